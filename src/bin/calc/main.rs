@@ -0,0 +1,527 @@
+use factorio_web_calculator::{data::*, solver::*};
+
+use clap::Parser;
+use serde::Serialize;
+use serde_json::from_reader;
+use std::{fs, fs::File, path::PathBuf, process::exit};
+
+#[derive(Debug, Parser)]
+#[clap(about, long_about = Some("Runs the calculator without the web interface and prints the production steps"))]
+struct CliParameters {
+    /// Processed game data made by data-process
+    #[clap(
+        short,
+        long,
+        parse(from_os_str),
+        value_name = "DATA",
+        default_value = "assets/generated/processed-data.json"
+    )]
+    data_file: PathBuf,
+    /// Solver settings in JSON, command line preferences are applied on top of them
+    #[clap(short, long, parse(from_os_str), value_name = "SETTINGS")]
+    settings_file: Option<PathBuf>,
+    /// Targets in the form ITEM=AMOUNT, where AMOUNT is a number of machines or a rate ending with /s or /m
+    #[clap(short, long = "target", value_name = "ITEM=AMOUNT", parse(try_from_str = parse_target))]
+    targets: Vec<CalcTarget>,
+    /// File with one target per line in the same form as --target, lines starting with # are ignored
+    #[clap(long, parse(from_os_str), value_name = "TARGETS")]
+    targets_file: Option<PathBuf>,
+    /// Labs researching a technology, in the form TECHNOLOGY=LABS
+    #[clap(long = "research", value_name = "TECHNOLOGY=LABS", parse(try_from_str = parse_research))]
+    research: Vec<ResearchTarget>,
+    /// Science per minute of every science pack the labs accept
+    #[clap(long, value_name = "SPM")]
+    spm: Option<f64>,
+    /// Machine for a recipe or resource category
+    #[clap(short, long = "machine", value_name = "CATEGORY=MACHINE", parse(try_from_str = parse_pair))]
+    machines: Vec<(String, String)>,
+    /// Recipe for an item
+    #[clap(short, long = "recipe", value_name = "ITEM=RECIPE", parse(try_from_str = parse_pair))]
+    recipes: Vec<(String, String)>,
+    /// Offshore pump or recipe, like a water well, supplying a fluid
+    #[clap(long = "water-source", value_name = "FLUID=SOURCE", parse(try_from_str = parse_pair))]
+    water_sources: Vec<(String, String)>,
+    /// Generator making electricity, boilers heating steam to its temperature are preferred
+    #[clap(long, value_name = "GENERATOR")]
+    generator: Option<String>,
+    /// Item supplied from outside of the factory
+    #[clap(short, long = "import", value_name = "ITEM")]
+    imports: Vec<String>,
+    /// Limit on an input in items per second, the item is supplied from outside like imported items
+    #[clap(short, long = "limit", value_name = "ITEM=RATE", parse(try_from_str = parse_limit))]
+    limits: Vec<(String, f64)>,
+    /// Make the targets as fast as the limits allow, target rates only set the ratio between them
+    #[clap(long)]
+    maximize: bool,
+    /// Belt used to count belts of item flows, the slowest belt is used if it's not found
+    #[clap(long, value_name = "BELT", default_value = "transport-belt")]
+    belt: String,
+    /// Inserter used to count inserters of machines, the slowest inserter is used if it's not found
+    #[clap(long, value_name = "INSERTER", default_value = "inserter")]
+    inserter: String,
+    /// Extra items carried by inserters in each swing, stack inserters use --stack-inserter-bonus
+    #[clap(long, value_name = "BONUS", default_value = "0")]
+    inserter_stack_bonus: u32,
+    /// Extra items carried by stack inserters in each swing
+    #[clap(long, value_name = "BONUS", default_value = "0")]
+    stack_inserter_bonus: u32,
+    /// Round trip time of trains bringing in the inputs in seconds, trains are only counted if it's given
+    #[clap(long, value_name = "SECONDS")]
+    trip_time: Option<f64>,
+    /// Number of wagons in each train
+    #[clap(long, value_name = "WAGONS", default_value = "4")]
+    train_wagons: u32,
+    /// Average distance logistic robots fly in tiles, robots are only counted if it's given
+    #[clap(long, value_name = "TILES")]
+    robot_distance: Option<f64>,
+    /// Worker robot speed research level
+    #[clap(long, value_name = "LEVEL", default_value = "0")]
+    robot_speed_level: u32,
+    /// Worker robot cargo size research level
+    #[clap(long, value_name = "LEVEL", default_value = "0")]
+    robot_cargo_level: u32,
+    /// Fuel burnt by machines with burner energy sources
+    #[clap(long, value_name = "FUEL")]
+    fuel: Option<String>,
+    /// Levels of mining productivity research
+    #[clap(long, value_name = "LEVEL")]
+    mining_productivity: Option<u32>,
+    /// Yield of an infinite resource like crude oil in percent of its normal amount
+    #[clap(long = "yield", value_name = "RESOURCE=PERCENT", parse(try_from_str = parse_yield))]
+    yields: Vec<(String, f64)>,
+    /// Use expensive recipes
+    #[clap(long)]
+    expensive: bool,
+    /// Print the result as JSON
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    steps: Vec<StepReport>,
+    flows: Vec<FlowReport>,
+    inputs: Vec<(String, f64)>,
+    /// Belts needed to bring in the inputs, fluids are left out
+    input_belts: Vec<(String, f64)>,
+    /// Wagons and trains needed to bring in the inputs
+    input_trains: Vec<(String, TrainEstimate)>,
+    /// Logistic robots carrying every item to the steps consuming it
+    robots: Vec<(String, f64)>,
+    outputs: Vec<(String, f64)>,
+    surplus: Vec<(String, f64)>,
+    /// Input limits that are used up completely
+    binding_limits: Vec<(String, f64)>,
+    /// Electric power in watts, missing when the game data has no energy for some machine
+    power: Option<f64>,
+    /// Pollution per minute, missing when the game data has no emissions for some machine
+    pollution: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct StepReport {
+    machine: String,
+    recipe: String,
+    amount: f64,
+    modules: Vec<String>,
+    beacons: Option<BeaconSetting>,
+    fuel: Option<String>,
+    /// Inserters each machine needs
+    inserters: Option<usize>,
+    produced: Vec<(String, f64)>,
+    consumed: Vec<(String, f64)>,
+    power: Option<f64>,
+    pollution: Option<f64>,
+}
+
+/// Item moved from one step to another, steps are indices into the list of steps
+#[derive(Debug, Serialize)]
+struct FlowReport {
+    item: String,
+    from: usize,
+    to: usize,
+    rate: f64,
+    fluid: bool,
+    belts: Option<f64>,
+    lanes: Option<f64>,
+    pipelines: Option<usize>,
+    /// Longest pipe between pumps, missing if any length is fine
+    max_pipe_length: Option<u32>,
+}
+
+impl StepReport {
+    fn new(step: &CalcStep, inserters: Option<&InserterCounter>) -> Self {
+        let modules = step.factory.modules();
+        Self {
+            machine: step.machine_name(),
+            recipe: step.factory.recipe_name(),
+            amount: step.amount,
+            modules: modules.map(|m| m.names()).unwrap_or_default(),
+            beacons: modules.and_then(|m| m.beacon_setting()),
+            fuel: step.factory.fuel.map(|fuel| fuel.name.clone()),
+            inserters: inserters.map(|inserters| step.inserters_per_machine(inserters)),
+            produced: step.produced_per_sec(),
+            consumed: step.consumed_per_sec(),
+            power: step.power_usage(),
+            pollution: step.pollution_per_minute(),
+        }
+    }
+}
+
+fn parse_pair(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+            Ok((key.trim().into(), value.trim().into()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got {}", s)),
+    }
+}
+
+fn parse_amount(amount: &str) -> Result<f64, String> {
+    amount
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid amount {}: {}", amount, e))
+}
+
+/// Rate ending with /s or /m in items per second
+fn parse_rate(rate: &str) -> Option<Result<f64, String>> {
+    if let Some(rate) = rate.strip_suffix("/s") {
+        Some(parse_amount(rate))
+    } else {
+        rate.strip_suffix("/m")
+            .map(|rate| parse_amount(rate).map(|rate| rate / 60.0))
+    }
+}
+
+fn parse_target(s: &str) -> Result<CalcTarget, String> {
+    let (item, amount) = parse_pair(s)?;
+    let rate = match parse_rate(&amount) {
+        Some(rate) => CalcTargetRate::ItemsPerSecond(rate?),
+        None => CalcTargetRate::Factories(parse_amount(&amount)?),
+    };
+    Ok(CalcTarget::new(item, rate))
+}
+
+fn parse_limit(s: &str) -> Result<(String, f64), String> {
+    let (item, rate) = parse_pair(s)?;
+    let rate = parse_rate(&rate).unwrap_or_else(|| parse_amount(&rate))?;
+    Ok((item, rate))
+}
+
+fn parse_yield(s: &str) -> Result<(String, f64), String> {
+    let (resource, percent) = parse_pair(s)?;
+    let percent = parse_amount(percent.trim_end_matches('%'))?;
+    Ok((resource, percent))
+}
+
+fn parse_research(s: &str) -> Result<ResearchTarget, String> {
+    let (technology, labs) = parse_pair(s)?;
+    Ok(ResearchTarget::Labs {
+        technology,
+        labs: labs
+            .parse()
+            .map_err(|e| format!("invalid amount of labs {}: {}", labs, e))?,
+    })
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    exit(1)
+}
+
+fn print_items(title: &str, items: &[(String, f64)]) {
+    if items.is_empty() {
+        return;
+    }
+    println!("{}:", title);
+    for (name, amount) in items {
+        println!("    {:>12.3}/s {}", amount, name);
+    }
+}
+
+fn print_report(report: &Report) {
+    println!("Steps:");
+    for (idx, step) in report.steps.iter().enumerate() {
+        print!("{:>3} {:>12.3} x {}", idx, step.amount, step.machine);
+        if step.recipe != step.machine {
+            print!(" ({})", step.recipe);
+        }
+        if !step.modules.is_empty() {
+            print!(" modules: {}", step.modules.join(", "));
+        }
+        if let Some(beacons) = &step.beacons {
+            print!(
+                " beacons: {} x {} [{}]",
+                beacons.count,
+                beacons.beacon,
+                beacons.modules.join(", ")
+            );
+        }
+        if let Some(fuel) = &step.fuel {
+            print!(" fuel: {}", fuel);
+        }
+        if let Some(inserters) = step.inserters {
+            print!(" inserters per machine: {}", inserters);
+        }
+        println!();
+        let produced: Vec<String> = step
+            .produced
+            .iter()
+            .map(|(name, amount)| format!("{:.3}/s {}", amount, name))
+            .collect();
+        let consumed: Vec<String> = step
+            .consumed
+            .iter()
+            .map(|(name, amount)| format!("{:.3}/s {}", amount, name))
+            .collect();
+        println!("        produces: {}", produced.join(", "));
+        if !consumed.is_empty() {
+            println!("        consumes: {}", consumed.join(", "));
+        }
+    }
+    if !report.flows.is_empty() {
+        println!("Flows:");
+        for flow in &report.flows {
+            print!(
+                "    {:>3} -> {:<3} {:>12.3}/s {}",
+                flow.from, flow.to, flow.rate, flow.item
+            );
+            if let (Some(belts), Some(lanes)) = (flow.belts, flow.lanes) {
+                print!(" ({:.3} belts, {:.3} lanes)", belts, lanes);
+            }
+            if let Some(pipelines) = flow.pipelines {
+                print!(" ({} pipelines", pipelines);
+                if let Some(length) = flow.max_pipe_length {
+                    print!(", pump every {} pipes", length);
+                }
+                print!(")");
+            }
+            println!();
+        }
+    }
+    print_items("Inputs", &report.inputs);
+    if !report.input_belts.is_empty() {
+        println!("Input belts:");
+        for (name, belts) in &report.input_belts {
+            println!("    {:>12.3} x {}", belts, name);
+        }
+    }
+    if !report.input_trains.is_empty() {
+        println!("Input trains:");
+        for (name, estimate) in &report.input_trains {
+            println!(
+                "    {:>12.3} trains ({:.3} wagons/m) x {}",
+                estimate.trains, estimate.wagons_per_minute, name
+            );
+        }
+    }
+    if !report.robots.is_empty() {
+        println!("Logistic robots:");
+        for (name, robots) in &report.robots {
+            println!("    {:>12.3} x {}", robots, name);
+        }
+        let total: f64 = report.robots.iter().map(|(_, robots)| robots).sum();
+        println!("    {:>12.3} total", total);
+    }
+    print_items("Outputs", &report.outputs);
+    print_items("Surplus", &report.surplus);
+    print_items("Limited by", &report.binding_limits);
+    match report.power {
+        Some(power) => println!("Power: {:.3} MW", power / 1e6),
+        None => println!("Power: unknown"),
+    }
+    match report.pollution {
+        Some(pollution) => println!("Pollution: {:.3}/m", pollution),
+        None => println!("Pollution: unknown"),
+    }
+}
+
+fn main() {
+    let params = CliParameters::parse();
+
+    let data_file = File::open(&params.data_file).unwrap_or_else(|e| {
+        fail(format!(
+            "Failed to open {}: {}",
+            params.data_file.display(),
+            e
+        ))
+    });
+    let game_data: GameData =
+        from_reader(data_file).unwrap_or_else(|e| fail(format!("Failed to read game data: {}", e)));
+
+    let mut settings: SolverSettings = match &params.settings_file {
+        Some(path) => {
+            let settings_file = File::open(path)
+                .unwrap_or_else(|e| fail(format!("Failed to open {}: {}", path.display(), e)));
+            from_reader(settings_file)
+                .unwrap_or_else(|e| fail(format!("Failed to read settings: {}", e)))
+        }
+        None => SolverSettings::default(),
+    };
+    if params.expensive {
+        settings.difficulty = Difficulty::Expensive;
+    }
+    if params.fuel.is_some() {
+        settings.fuel = params.fuel;
+    }
+    for (category, machine) in params.machines {
+        if game_data.assembling_machines.contains_key(&machine) {
+            settings.assembling_machines.insert(category, machine);
+        } else if game_data.mining_drills.contains_key(&machine) {
+            settings.mining_drills.insert(category, machine);
+        } else {
+            fail(format!("Machine {} not found in game data", machine));
+        }
+    }
+    settings.item_recipes.extend(params.recipes);
+    for (fluid, source) in params.water_sources {
+        if !game_data.offshore_pumps.contains_key(&source)
+            && !game_data.recipes.contains_key(&source)
+        {
+            fail(format!("Water source {} not found in game data", source));
+        }
+        settings.water_sources.insert(fluid, source);
+    }
+    if let Some(generator) = params.generator {
+        if !game_data.generators.contains_key(&generator) {
+            fail(format!("Generator {} not found in game data", generator));
+        }
+        settings.generator = Some(generator);
+    }
+    if let Some(level) = params.mining_productivity {
+        settings.mining_productivity_level = level;
+    }
+    for (resource, percent) in params.yields {
+        match game_data.resources.get(&resource) {
+            Some(found) if found.infinite => {
+                settings.resource_yields.insert(resource, percent);
+            }
+            Some(_) => fail(format!("Resource {} is not infinite", resource)),
+            None => fail(format!("Resource {} not found in game data", resource)),
+        }
+    }
+
+    let mut targets = params.targets;
+    if let Some(path) = &params.targets_file {
+        let contents = fs::read_to_string(path)
+            .unwrap_or_else(|e| fail(format!("Failed to read {}: {}", path.display(), e)));
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            targets.push(parse_target(line).unwrap_or_else(|e| fail(e)));
+        }
+    }
+    let mut research = params.research;
+    if let Some(spm) = params.spm {
+        let science_packs = game_data.science_packs();
+        if science_packs.is_empty() {
+            fail("--spm needs labs to know the science packs, the game data has no labs".into());
+        }
+        research.push(ResearchTarget::SciencePerMinute { science_packs, spm });
+    }
+    for research in &research {
+        if let ResearchTarget::Labs { technology, .. } = research {
+            if !game_data.technologies.contains_key(technology) {
+                fail(format!("Technology {} not found in game data", technology));
+            }
+        }
+        targets.extend(research.science_targets(&game_data, settings.difficulty));
+    }
+    if targets.is_empty() {
+        fail("No targets given, use --target, --targets-file, --research or --spm".into());
+    }
+
+    let calculation = Calculation::new(&game_data, settings)
+        .with_imported_items(params.imports)
+        .with_input_limits(params.limits);
+    let calculation = if params.maximize {
+        calculation.maximize(&targets)
+    } else {
+        calculation.solve(&targets)
+    }
+    .unwrap_or_else(|e| fail(format!("An error occurred: {}", e)));
+
+    let belts = BeltCounter::new(&game_data, &params.belt);
+    let pipes = PipeCounter::new(&game_data);
+    let trains = TrainCounter::new(&game_data);
+    let inserters = InserterCounter::new(
+        &game_data,
+        &params.inserter,
+        params.inserter_stack_bonus,
+        params.stack_inserter_bonus,
+    );
+    let robots = params.robot_distance.and_then(|distance| {
+        RobotCounter::new(
+            &game_data,
+            RobotSetting {
+                distance,
+                speed_level: params.robot_speed_level,
+                cargo_level: params.robot_cargo_level,
+            },
+        )
+    });
+    let graph = calculation.graph();
+    // Flows refer to positions in the printed list of steps
+    let order: Vec<usize> = graph.topological_order().into_iter().rev().collect();
+    let position = |node: usize| order.iter().position(|&idx| idx == node).unwrap_or(node);
+    let inputs = calculation.inputs();
+    let report = Report {
+        steps: order
+            .iter()
+            .map(|&idx| StepReport::new(&graph.nodes[idx], inserters.as_ref()))
+            .collect(),
+        flows: graph
+            .edges
+            .iter()
+            .map(|flow| FlowReport {
+                item: flow.item.clone(),
+                from: position(flow.from),
+                to: position(flow.to),
+                rate: flow.rate,
+                fluid: flow.fluid,
+                belts: belts
+                    .as_ref()
+                    .filter(|_| !flow.fluid)
+                    .and_then(|b| b.belts(&flow.item, flow.rate)),
+                lanes: belts
+                    .as_ref()
+                    .filter(|_| !flow.fluid)
+                    .and_then(|b| b.lanes(&flow.item, flow.rate)),
+                pipelines: flow.fluid.then(|| pipes.pipelines(flow.rate)),
+                max_pipe_length: pipes.max_pipe_length(flow.rate).filter(|_| flow.fluid),
+            })
+            .collect(),
+        input_belts: inputs
+            .iter()
+            .filter_map(|(name, rate)| {
+                let belts = belts.as_ref()?.belts(name, *rate)?;
+                Some((name.clone(), belts))
+            })
+            .collect(),
+        input_trains: inputs
+            .iter()
+            .filter_map(|(name, rate)| {
+                let estimate =
+                    trains.estimate(name, *rate, params.trip_time?, params.train_wagons)?;
+                Some((name.clone(), estimate))
+            })
+            .collect(),
+        robots: robots
+            .map(|robots| robots.robots_per_item(&calculation))
+            .unwrap_or_default(),
+        inputs,
+        outputs: calculation.outputs(),
+        surplus: calculation.surplus(),
+        binding_limits: calculation.binding_limits(),
+        power: calculation.power_usage(),
+        pollution: calculation.pollution_per_minute(),
+    };
+
+    if params.json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        print_report(&report);
+    }
+}
@@ -5,6 +5,7 @@ use image::{
     imageops::overlay, io::Reader, GenericImageView, ImageBuffer, ImageFormat, Pixel, Rgba,
     RgbaImage,
 };
+use serde::de::DeserializeOwned;
 use serde_json::{from_reader, from_value, to_writer, Value};
 use std::{
     collections::{HashMap, HashSet},
@@ -180,6 +181,138 @@ fn main() {
         )
     });
 
+    println!("Processing icons for beacons");
+    game_data.beacons.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "beacon",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for transport belts");
+    game_data.transport_belts.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "transport-belt",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for pumps");
+    game_data.pumps.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "pump",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for inserters");
+    game_data.inserters.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "inserter",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for logistic robots");
+    game_data.logistic_robots.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "logistic-robot",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for wagons");
+    game_data.cargo_wagons.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "cargo-wagon",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+    game_data.fluid_wagons.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "fluid-wagon",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for boilers");
+    game_data.boilers.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "boiler",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for generators");
+    game_data.generators.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "generator",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for reactors");
+    game_data.reactors.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "reactor",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for labs");
+    game_data.labs.iter().for_each(|(_, item)| {
+        insert_icon(
+            &item.icon,
+            &item.name,
+            "lab",
+            &mut complex_icons,
+            &mut simple_icons,
+        )
+    });
+
+    println!("Processing icons for recipes");
+    game_data.recipes.iter().for_each(|(_, recipe)| {
+        if let Some(icon) = &recipe.icon {
+            insert_icon(
+                icon,
+                &recipe.name,
+                "recipe",
+                &mut complex_icons,
+                &mut simple_icons,
+            )
+        }
+    });
+
     println!("Processing complex icons");
     let complex_icons: HashMap<String, RgbaImage> = complex_icons
         .into_iter()
@@ -310,7 +443,7 @@ fn tint_pixel(pixel: &mut Rgba<u8>, tint: &TintColor) {
 
 fn get_data(json_data: &Value) -> GameData {
     println!("Processing items");
-    let items: HashMap<String, Item> = json_data["item"]
+    let mut items: HashMap<String, Item> = json_data["item"]
         .as_object()
         .cloned()
         .unwrap()
@@ -329,11 +462,16 @@ fn get_data(json_data: &Value) -> GameData {
     println!("Processing furnaces");
     let furnaces: HashMap<String, AssemblingMachine> =
         from_value(json_data["furnace"].clone()).unwrap();
-    println!("Merging furnaces and assembling machines");
+    println!("Processing rocket silos");
+    let rocket_silo_machines: HashMap<String, AssemblingMachine> =
+        optional_section(json_data, "rocket-silo");
+    let rocket_silos: HashMap<String, RocketSilo> = optional_section(json_data, "rocket-silo");
+    println!("Merging furnaces, rocket silos and assembling machines");
     let assembling_machines: HashMap<String, AssemblingMachine> = {
         assembling_machines
             .into_iter()
             .chain(furnaces.into_iter())
+            .chain(rocket_silo_machines)
             .collect()
     };
 
@@ -356,6 +494,59 @@ fn get_data(json_data: &Value) -> GameData {
 
     println!("Processing resources");
     let resources: HashMap<String, Resource> = from_value(json_data["resource"].clone()).unwrap();
+    resources
+        .values()
+        .filter(|resource| resource.infinite && resource.normal.is_none())
+        .for_each(|resource| {
+            println!(
+                "Infinite resource {} has no normal amount, its yield can't be set",
+                resource.name
+            )
+        });
+
+    println!("Processing modules");
+    let modules: HashMap<String, Module> = from_value(json_data["module"].clone()).unwrap();
+
+    println!("Processing beacons");
+    let beacons: HashMap<String, Beacon> = optional_section(json_data, "beacon");
+
+    println!("Processing labs and technologies");
+    let labs: HashMap<String, Lab> = optional_section(json_data, "lab");
+    let technologies: HashMap<String, Technology> = optional_section(json_data, "technology");
+
+    println!("Processing transport belts");
+    let transport_belts: HashMap<String, TransportBelt> =
+        optional_section(json_data, "transport-belt");
+
+    println!("Processing pumps");
+    let pumps: HashMap<String, Pump> = optional_section(json_data, "pump");
+
+    println!("Processing inserters");
+    let inserters: HashMap<String, Inserter> = optional_section(json_data, "inserter");
+
+    println!("Processing logistic robots");
+    let logistic_robots: HashMap<String, LogisticRobot> =
+        optional_section(json_data, "logistic-robot");
+
+    println!("Processing wagons");
+    let cargo_wagons: HashMap<String, CargoWagon> = optional_section(json_data, "cargo-wagon");
+    let fluid_wagons: HashMap<String, FluidWagon> = optional_section(json_data, "fluid-wagon");
+
+    println!("Processing power generation");
+    let boilers: HashMap<String, Boiler> = optional_section(json_data, "boiler");
+    let generators: HashMap<String, Generator> = optional_section(json_data, "generator");
+    let reactors: HashMap<String, Reactor> = optional_section(json_data, "reactor");
+
+    // Energy pseudo items can be chosen as targets, they borrow icons of their producers
+    if let Some(generator) = generators.values().min_by(|g1, g2| g1.name.cmp(&g2.name)) {
+        items.insert(
+            ELECTRIC_ENERGY.into(),
+            energy_item(ELECTRIC_ENERGY, &generator.icon),
+        );
+    }
+    if let Some(reactor) = reactors.values().min_by(|r1, r2| r1.name.cmp(&r2.name)) {
+        items.insert(HEAT_ENERGY.into(), energy_item(HEAT_ENERGY, &reactor.icon));
+    }
 
     GameData {
         items,
@@ -366,5 +557,43 @@ fn get_data(json_data: &Value) -> GameData {
         mining_drills,
         offshore_pumps,
         resources,
+        modules,
+        beacons,
+        boilers,
+        generators,
+        reactors,
+        rocket_silos,
+        labs,
+        technologies,
+        transport_belts,
+        pumps,
+        inserters,
+        logistic_robots,
+        cargo_wagons,
+        fluid_wagons,
+    }
+}
+
+fn energy_item(name: &str, icon: &Icon) -> Item {
+    Item {
+        icon: icon.clone(),
+        name: name.into(),
+        subgroup: "energy".into(),
+        fuel_value: 0.0,
+        fuel_category: None,
+        stack_size: 0,
+        rocket_launch_product: None,
+        rocket_launch_products: Vec::new(),
+    }
+}
+
+/// Sections that older data dumps don't have are treated as empty
+fn optional_section<T: DeserializeOwned>(json_data: &Value, name: &str) -> HashMap<String, T> {
+    match &json_data[name] {
+        Value::Null => {
+            println!("Section {} is missing in input data, skipping", name);
+            HashMap::new()
+        }
+        section => from_value(section.clone()).unwrap(),
     }
 }
@@ -0,0 +1,115 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use super::{BeaconSetting, ModuleSlots, SpriteSheetIcon};
+use crate::constants::GAME_DATA;
+
+#[derive(Debug)]
+pub struct BeaconSettingInput;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct BeaconSettingInputProperties {
+    /// Empty beacon name means there are no beacons
+    pub setting: BeaconSetting,
+    // Radio buttons of different inputs must not share input names
+    pub group: String,
+    pub callback: Callback<BeaconSetting>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BeaconSettingInputMessage {
+    Beacon(String),
+    Count(f64),
+    Modules(Vec<String>),
+}
+
+impl Component for BeaconSettingInput {
+    type Message = BeaconSettingInputMessage;
+    type Properties = BeaconSettingInputProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let mut setting = props.setting.clone();
+        match msg {
+            BeaconSettingInputMessage::Beacon(beacon) => {
+                if beacon != setting.beacon {
+                    setting.modules.clear();
+                }
+                setting.beacon = beacon;
+            }
+            BeaconSettingInputMessage::Count(count) => setting.count = count,
+            BeaconSettingInputMessage::Modules(modules) => setting.modules = modules,
+        }
+        props.callback.emit(setting);
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+        let name = format!("beacon-{}", props.group);
+
+        let on_beacon_selected = link.batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|i| BeaconSettingInputMessage::Beacon(i.value()))
+        });
+
+        let on_count_change = link.batch_callback(|e: Event| {
+            e.target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|i| Some(BeaconSettingInputMessage::Count(i.value().parse().ok()?)))
+        });
+
+        let mut beacons: Vec<_> = GAME_DATA.beacons.values().collect();
+        beacons.sort_unstable_by(|b1, b2| b1.name.cmp(&b2.name));
+
+        let details = match GAME_DATA.beacons.get(&props.setting.beacon) {
+            Some(beacon) => {
+                let choices = GAME_DATA
+                    .modules_sorted()
+                    .into_iter()
+                    .filter(|module| beacon.accepts_module(module))
+                    .collect::<Vec<_>>();
+                html! {
+                    <>
+                    {"Beacons: "}
+                    <input type="text" onchange={on_count_change} value={props.setting.count.to_string()} />
+                    <ModuleSlots
+                        slots={beacon.module_slots()}
+                        selected={props.setting.modules.clone()}
+                        choices={choices}
+                        group={name.clone()}
+                        callback={link.callback(BeaconSettingInputMessage::Modules)} />
+                    </>
+                }
+            }
+            None => html! {},
+        };
+
+        html! {
+            <div class="beacon-setting">
+                <label>
+                    <input type="radio" name={name.clone()} value="" checked={props.setting.beacon.is_empty()} onchange={on_beacon_selected.clone()}/>
+                    {"no beacons"}
+                </label>
+                {
+                    for beacons.into_iter().map(|beacon| {
+                        html_nested! {
+                            <label>
+                                <input type="radio" name={name.clone()} value={beacon.name.clone()} checked={props.setting.beacon == beacon.name} onchange={on_beacon_selected.clone()}/>
+                                <SpriteSheetIcon prefix="beacon" name={beacon.name.clone()} />
+                            </label>
+                        }
+                    })
+                }
+                { details }
+            </div>
+        }
+    }
+}
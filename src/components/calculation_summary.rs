@@ -0,0 +1,139 @@
+use hashbrown::HashSet;
+use std::rc::Rc;
+use yew::prelude::*;
+
+use super::{trimmed, BeltCounter, ItemIcon, PipeCounter};
+
+#[derive(Debug)]
+pub struct CalculationSummary;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CalculationSummaryProperties {
+    pub inputs: Vec<(String, f64)>,
+    pub outputs: Vec<(String, f64)>,
+    pub surplus: Vec<(String, f64)>,
+    /// Input limits that are used up completely
+    #[prop_or_default]
+    pub binding_limits: Vec<(String, f64)>,
+    /// Electric power in watts, `None` when the game data has no energy for some machine
+    #[prop_or_default]
+    pub power: Option<f64>,
+    /// Pollution per minute, `None` when the game data has no emissions for some machine
+    #[prop_or_default]
+    pub pollution: Option<f64>,
+    /// Inputs and outputs are annotated with the belts they need
+    #[prop_or_default]
+    pub belts: Option<Rc<BeltCounter<'static>>>,
+    /// Fluid inputs and outputs are annotated with the pipelines they need
+    #[prop_or_default]
+    pub pipes: Option<Rc<PipeCounter<'static>>>,
+    #[prop_or_default]
+    pub fluids: HashSet<String>,
+}
+
+impl CalculationSummary {
+    fn item_list(
+        title: &str,
+        items: &[(String, f64)],
+        props: Option<&CalculationSummaryProperties>,
+    ) -> Html {
+        if items.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="summary-list">
+                <p>{ title }</p>
+                <ul>
+                {
+                    for items.iter().map(|(name, amount)| {
+                        html_nested! {
+                            <li>
+                                <ItemIcon item={name.clone()} />
+                                {format!("{}/s", trimmed(*amount))}
+                                {
+                                    match props {
+                                        Some(props) if props.fluids.contains(name) => pipes_label(props.pipes.as_deref(), *amount),
+                                        Some(props) => belts_label(props.belts.as_deref(), name, *amount),
+                                        None => String::new(),
+                                    }
+                                }
+                            </li>
+                        }
+                    })
+                }
+                </ul>
+            </div>
+        }
+    }
+}
+
+impl Component for CalculationSummary {
+    type Message = ();
+    type Properties = CalculationSummaryProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <div class="calculation-summary">
+                { Self::item_list("Inputs:", &props.inputs, Some(props)) }
+                { Self::item_list("Outputs:", &props.outputs, Some(props)) }
+                { Self::item_list("Surplus:", &props.surplus, None) }
+                { Self::item_list("Limited by:", &props.binding_limits, None) }
+                if let Some(power) = power_label(props.power) {
+                    <p>{format!("Power: {}", power)}</p>
+                }
+                if let Some(pollution) = pollution_label(props.pollution) {
+                    <p>{format!("Pollution: {}", pollution)}</p>
+                }
+            </div>
+        }
+    }
+}
+
+/// Power in megawatts, nothing when no power is used
+pub fn power_label(power: Option<f64>) -> Option<String> {
+    match power {
+        Some(power) if power > 0.0 => Some(format!("{} MW", trimmed(power / 1e6))),
+        Some(_) => None,
+        None => Some("unknown".into()),
+    }
+}
+
+/// Pollution per minute, nothing when there's no pollution
+pub fn pollution_label(pollution: Option<f64>) -> Option<String> {
+    match pollution {
+        Some(pollution) if pollution > 0.0 => Some(format!("{}/m", trimmed(pollution))),
+        Some(_) => None,
+        None => Some("unknown".into()),
+    }
+}
+
+/// Belts and lanes needed for an item flow, empty for fluids and energy
+pub fn belts_label(belts: Option<&BeltCounter>, item: &str, rate: f64) -> String {
+    match (
+        belts.and_then(|b| b.belts(item, rate)),
+        belts.and_then(|b| b.lanes(item, rate)),
+    ) {
+        (Some(belts), Some(lanes)) => {
+            format!(" ({} belts, {} lanes)", trimmed(belts), trimmed(lanes))
+        }
+        _ => String::new(),
+    }
+}
+
+/// Pipelines needed for a fluid flow and how far apart their pumps are
+pub fn pipes_label(pipes: Option<&PipeCounter>, rate: f64) -> String {
+    let pipes = match pipes {
+        Some(pipes) => pipes,
+        None => return String::new(),
+    };
+    let pipelines = pipes.pipelines(rate);
+    match pipes.max_pipe_length(rate) {
+        Some(length) => format!(" ({} pipelines, pump every {} pipes)", pipelines, length),
+        None => format!(" ({} pipelines)", pipelines),
+    }
+}
@@ -1,6 +1,6 @@
 use yew::prelude::*;
 
-use crate::components::{CalcTarget, Calculator, CalculatorMessage};
+use crate::components::{Calculator, CalculatorMessage};
 
 #[derive(Debug, Clone)]
 pub struct AddItem;
@@ -21,7 +21,7 @@ impl Component for AddItem {
     fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
         ctx.props()
             .onclick
-            .emit(CalculatorMessage::AddItem(CalcTarget::default()));
+            .emit(CalculatorMessage::AddItem(Calculator::default_target()));
         false
     }
 
@@ -1,13 +1,21 @@
+use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::components::{
-    CalcTargetRate, Calculator, CalculatorMessage, Factory, ItemSelectDropdown,
+use crate::{
+    components::{CalcTargetRate, Calculator, CalculatorMessage, ItemSelectDropdown},
+    constants::GAME_DATA,
+    solver::{Factory, Machine, SolverSettings},
+    WrappedUserSettings,
 };
 
-#[derive(Debug, Clone)]
-pub struct InputItem;
+#[derive(Debug)]
+pub struct InputItem {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct InputItemProps {
@@ -23,14 +31,23 @@ pub enum InputItemMessage {
     ItemSelected(String),
     Factories(f64),
     ItemsPerSecond(f64),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
 }
 
 impl Component for InputItem {
     type Message = InputItemMessage;
     type Properties = InputItemProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(InputItemMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
+
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -53,6 +70,9 @@ impl Component for InputItem {
                 ));
             }
             InputItemMessage::Remove => callback.emit(CalculatorMessage::RemoveItem(props.index)),
+            InputItemMessage::UserSettingsUpdated(user_settings) => {
+                self.user_settings = user_settings
+            }
         }
         true
     }
@@ -73,7 +93,20 @@ impl Component for InputItem {
                 .and_then(|i| Some(InputItemMessage::ItemsPerSecond(i.value().parse().ok()?)))
         });
 
-        let ips = Factory::ips_for_item(&props.item);
+        let settings: SolverSettings = self
+            .user_settings
+            .as_ref()
+            .and_then(|us| us.read().ok().map(|us| us.solver_settings()))
+            .unwrap_or_default();
+        let ips = Factory::ips_for_item(&GAME_DATA, &settings, &props.item);
+        // Amount of rocket launch steps is counted in rockets per minute
+        let factories_label = match Factory::for_item(&GAME_DATA, &settings, &props.item) {
+            Ok(Factory {
+                machine: Machine::RocketLaunch(_, _, _),
+                ..
+            }) => "Rockets/min: ",
+            _ => "Factories: ",
+        };
 
         html! {
             <li class="target" key = {props.index}>
@@ -82,7 +115,7 @@ impl Component for InputItem {
                 // Button to change this item's target
                 <ItemSelectDropdown index={props.index} selected_item={props.item.clone()} callback={link.callback(InputItemMessage::ItemSelected)} />
                 // Field to input amount of factories
-                {"Factories: "}
+                { factories_label }
                 <input type="text" onchange={on_factories_change} value={props.rate.as_factories(ips).to_string()} />
                 // Field to input Items Per Second
                 {"items/s: "}
@@ -1,43 +1,109 @@
-mod calc_step;
-mod calc_target;
-mod calc_target_rate;
-mod calculation;
-mod factory;
 mod input_list;
 
+use hashbrown::HashMap;
 use std::rc::Rc;
 
-pub use calc_step::*;
-pub use calc_target::*;
-pub use calc_target_rate::*;
-pub use calculation::*;
-use factorio_web_calculator::data::GameData;
-pub use factory::*;
+pub use crate::solver::*;
 pub use input_list::*;
 
 use crate::{
-    components::{FactoryStep, FactorySteps},
-    Route,
+    components::{
+        CalculationSummary, FactoryStep, FactorySteps, ImportedItems, InputLimits, ResearchTargets,
+        RobotEstimate, TrainPlanner,
+    },
+    constants::{DEFAULT_ITEM, DEFAULT_TRAIN_WAGONS, DEFAULT_TRIP_TIME, GAME_DATA},
+    data::GameData,
+    Route, WrappedUserSettings,
 };
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[derive(Debug)]
 pub struct Calculator {
     pub targets: Vec<CalcTarget>,
-    pub calculation: Option<Result<Calculation, CalculationError>>,
+    pub research_targets: Vec<ResearchTarget>,
+    pub imported_items: Vec<String>,
+    pub input_limits: Vec<(String, f64)>,
+    /// Targets are made as fast as the input limits allow
+    pub maximize: bool,
+    /// Round trip time of trains bringing in the inputs in seconds
+    pub train_trip_time: f64,
+    pub train_wagons: u32,
+    pub robot_setting: RobotSetting,
+    pub step_modules: HashMap<String, Vec<String>>,
+    pub step_beacons: HashMap<String, BeaconSetting>,
+    pub calculation: Option<Result<Calculation<'static>, CalculationError>>,
     #[allow(dead_code)]
     game_data_context_listener: ContextHandle<Option<Rc<GameData>>>,
     pub game_data: Option<Rc<GameData>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+    pub user_settings: Option<Rc<WrappedUserSettings>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CalculatorMessage {
     GameDataUpdated(Option<Rc<GameData>>),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
     RemoveItem(usize),
     AddItem(CalcTarget),
     ChangeItem(usize, String),
     ChangeRate(usize, CalcTargetRate),
+    AddResearch,
+    RemoveResearch(usize),
+    ChangeResearch(usize, ResearchTarget),
+    AddImport,
+    RemoveImport(usize),
+    ChangeImport(usize, String),
+    AddLimit,
+    RemoveLimit(usize),
+    ChangeLimit(usize, (String, f64)),
+    ToggleMaximize(bool),
+    ChangeTripTime(f64),
+    ChangeTrainWagons(u32),
+    ChangeRobotSetting(RobotSetting),
+    ChangeStepModules(String, Vec<String>),
+    ChangeStepBeacons(String, BeaconSetting),
+}
+
+impl Calculator {
+    fn solver_settings(&self) -> SolverSettings {
+        self.user_settings
+            .as_ref()
+            .and_then(|us| us.read().ok().map(|us| us.solver_settings()))
+            .unwrap_or_default()
+    }
+
+    fn default_imported_items(&self) -> Vec<String> {
+        self.user_settings
+            .as_ref()
+            .and_then(|us| us.read().ok().map(|us| us.imported_items().to_vec()))
+            .unwrap_or_default()
+    }
+
+    pub fn default_target() -> CalcTarget {
+        CalcTarget::new(DEFAULT_ITEM, CalcTargetRate::default())
+    }
+
+    /// Targets of the calculation, including science packs consumed by research
+    fn all_targets(&self) -> Vec<CalcTarget> {
+        let difficulty = self
+            .user_settings
+            .as_ref()
+            .and_then(|us| us.read().ok().map(|us| us.difficulty()))
+            .unwrap_or_default();
+        self.targets
+            .iter()
+            .cloned()
+            .chain(
+                self.research_targets
+                    .iter()
+                    .flat_map(|research| research.science_targets(&GAME_DATA, difficulty)),
+            )
+            .collect()
+    }
 }
 
 impl Component for Calculator {
@@ -49,26 +115,54 @@ impl Component for Calculator {
         let (game_data, game_data_context_listener) = scope
             .context(scope.callback(CalculatorMessage::GameDataUpdated))
             .expect("Game Data context was not provided");
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(CalculatorMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
 
         Self {
             targets: vec![],
+            research_targets: vec![],
+            imported_items: vec![],
+            input_limits: vec![],
+            maximize: false,
+            train_trip_time: DEFAULT_TRIP_TIME,
+            train_wagons: DEFAULT_TRAIN_WAGONS,
+            robot_setting: RobotSetting::default(),
+            step_modules: HashMap::new(),
+            step_beacons: HashMap::new(),
             calculation: None,
             game_data,
             game_data_context_listener,
+            user_settings,
+            user_settings_context_listener,
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match (&self.game_data, msg) {
             (None, CalculatorMessage::GameDataUpdated(game_data)) => {
-                self.targets = vec![CalcTarget::default()];
+                self.targets = vec![Self::default_target()];
+                self.imported_items = self.default_imported_items();
                 self.game_data = game_data
             }
             (Some(_), CalculatorMessage::GameDataUpdated(game_data)) => {
                 log::warn!("Changed game data when it is already set");
-                self.targets = vec![CalcTarget::default()];
+                self.targets = vec![Self::default_target()];
+                self.imported_items = self.default_imported_items();
                 self.game_data = game_data
             }
+            (game_data, CalculatorMessage::UserSettingsUpdated(user_settings)) => {
+                // Settings arrive after the game data, imported items default to the ones from them
+                if self.user_settings.is_none() {
+                    self.user_settings = user_settings;
+                    self.imported_items = self.default_imported_items();
+                } else {
+                    self.user_settings = user_settings;
+                }
+                if game_data.is_none() {
+                    return true;
+                }
+            }
             // If game data is not available then other messages don't change anything
             (None, _) => return false,
             (Some(_), CalculatorMessage::AddItem(target)) => {
@@ -83,32 +177,128 @@ impl Component for Calculator {
             (Some(_), CalculatorMessage::ChangeRate(idx, rate)) => {
                 self.targets[idx].rate = rate;
             }
+            (Some(_), CalculatorMessage::AddResearch) => {
+                self.research_targets
+                    .push(ResearchTarget::science_per_minute(&GAME_DATA, 60.0));
+            }
+            (Some(_), CalculatorMessage::RemoveResearch(idx)) => {
+                self.research_targets.remove(idx);
+            }
+            (Some(_), CalculatorMessage::ChangeResearch(idx, research)) => {
+                self.research_targets[idx] = research;
+            }
+            (Some(_), CalculatorMessage::AddImport) => {
+                self.imported_items.push(DEFAULT_ITEM.into());
+            }
+            (Some(_), CalculatorMessage::RemoveImport(idx)) => {
+                self.imported_items.remove(idx);
+            }
+            (Some(_), CalculatorMessage::ChangeImport(idx, name)) => {
+                self.imported_items[idx] = name;
+            }
+            (Some(_), CalculatorMessage::AddLimit) => {
+                self.input_limits.push((DEFAULT_ITEM.into(), 1.0));
+            }
+            (Some(_), CalculatorMessage::RemoveLimit(idx)) => {
+                self.input_limits.remove(idx);
+            }
+            (Some(_), CalculatorMessage::ChangeLimit(idx, limit)) => {
+                self.input_limits[idx] = limit;
+            }
+            (Some(_), CalculatorMessage::ToggleMaximize(maximize)) => {
+                self.maximize = maximize;
+            }
+            // Trains don't change the calculation
+            (Some(_), CalculatorMessage::ChangeTripTime(trip_time)) => {
+                self.train_trip_time = trip_time;
+                return true;
+            }
+            (Some(_), CalculatorMessage::ChangeTrainWagons(wagons)) => {
+                self.train_wagons = wagons;
+                return true;
+            }
+            (Some(_), CalculatorMessage::ChangeRobotSetting(setting)) => {
+                self.robot_setting = setting;
+                return true;
+            }
+            (Some(_), CalculatorMessage::ChangeStepModules(step, modules)) => {
+                self.step_modules.insert(step, modules);
+            }
+            (Some(_), CalculatorMessage::ChangeStepBeacons(step, beacons)) => {
+                self.step_beacons.insert(step, beacons);
+            }
         }
-        self.calculation = Some(Calculation::default().solve(&self.targets));
+        let calculation = Calculation::new(&GAME_DATA, self.solver_settings())
+            .with_imported_items(self.imported_items.iter().cloned())
+            .with_input_limits(self.input_limits.iter().cloned())
+            .with_module_overrides(self.step_modules.clone())
+            .with_beacon_overrides(self.step_beacons.clone());
+        self.calculation = Some(if self.maximize {
+            calculation.maximize(&self.all_targets())
+        } else {
+            calculation.solve(&self.all_targets())
+        });
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let targets = &self.targets;
-        // Silenced the warning because sorting the resulting steps is planned
-        #[allow(unused_mut)]
-        let mut steps: Vec<CalcStep> = self
+        let steps: Vec<CalcStep<'static>> = self
+            .calculation
+            .as_ref()
+            .and_then(|rescalc| rescalc.as_ref().ok())
+            .map(|calc| calc.graph().ordered_steps())
+            .unwrap_or_default();
+        let user_settings = self.user_settings.as_ref().and_then(|us| us.read().ok());
+        let belts = user_settings
+            .as_ref()
+            .and_then(|us| BeltCounter::new(&GAME_DATA, us.belt()))
+            .map(Rc::new);
+        let pipes = Some(Rc::new(PipeCounter::new(&GAME_DATA)));
+        let inserters = user_settings
+            .as_ref()
+            .and_then(|us| {
+                InserterCounter::new(
+                    &GAME_DATA,
+                    us.inserter(),
+                    us.inserter_stack_bonus(),
+                    us.stack_inserter_bonus(),
+                )
+            })
+            .map(Rc::new);
+        let link = ctx.link();
+        let trains = Rc::new(TrainCounter::new(&GAME_DATA));
+        let robots = RobotCounter::new(&GAME_DATA, self.robot_setting.clone());
+        let summary = self
             .calculation
             .as_ref()
             .and_then(|rescalc| rescalc.as_ref().ok())
             .map(|calc| {
-                calc.steps
-                    .iter()
-                    .map(|(factory, amount)| CalcStep {
-                        factory: factory.clone(),
-                        amount: *amount,
-                    })
-                    .collect()
+                html! {
+                    <>
+                    <CalculationSummary inputs={calc.inputs()} outputs={calc.outputs()} surplus={calc.surplus()} binding_limits={calc.binding_limits()} power={calc.power_usage()} pollution={calc.pollution_per_minute()} belts={belts.clone()} pipes={pipes.clone()} fluids={calc.fluids()} />
+                    <TrainPlanner
+                        inputs={calc.inputs()}
+                        trains={trains.clone()}
+                        trip_time={self.train_trip_time}
+                        wagons={self.train_wagons}
+                        on_trip_time_change={link.callback(CalculatorMessage::ChangeTripTime)}
+                        on_wagons_change={link.callback(CalculatorMessage::ChangeTrainWagons)} />
+                    if let Some(robots) = &robots {
+                        <RobotEstimate
+                            robots={robots.robots_per_item(calc)}
+                            setting={self.robot_setting.clone()}
+                            on_change={link.callback(CalculatorMessage::ChangeRobotSetting)} />
+                    }
+                    </>
+                }
             })
             .unwrap_or_default();
-        // Commented out because this doesn't work well with optimized sorting algos
-        //steps.sort_by(|cs1, cs2| cs1.factory.sort_by(&cs2.factory));
-        let link = ctx.link();
+        let on_maximize_toggled = link.batch_callback(|e: Event| {
+            e.target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .map(|i| CalculatorMessage::ToggleMaximize(i.checked()))
+        });
         log::info!("number of steps: {}", steps.len());
         let status_message = if let Some(rescalc) = &self.calculation {
             if let Err(why) = rescalc {
@@ -137,15 +327,48 @@ impl Component for Calculator {
                 }) }
                 <AddItem onclick={link.callback(|m| m)}/>
                 </InputList>
+                <p> { "Research:" } </p>
+                <ResearchTargets
+                    targets={self.research_targets.clone()}
+                    on_add={link.callback(|_| CalculatorMessage::AddResearch)}
+                    on_remove={link.callback(CalculatorMessage::RemoveResearch)}
+                    on_change={link.callback(|(i, research)| CalculatorMessage::ChangeResearch(i, research))} />
+                <p> { "Imported items:" } </p>
+                <ImportedItems
+                    items={self.imported_items.clone()}
+                    group="import"
+                    on_add={link.callback(|_| CalculatorMessage::AddImport)}
+                    on_remove={link.callback(CalculatorMessage::RemoveImport)}
+                    on_change={link.callback(|(i, item)| CalculatorMessage::ChangeImport(i, item))} />
+                <p> { "Input limits:" } </p>
+                <InputLimits
+                    limits={self.input_limits.clone()}
+                    on_add={link.callback(|_| CalculatorMessage::AddLimit)}
+                    on_remove={link.callback(CalculatorMessage::RemoveLimit)}
+                    on_change={link.callback(|(i, limit)| CalculatorMessage::ChangeLimit(i, limit))} />
+                <label>
+                    <input type="checkbox" checked={self.maximize} onchange={on_maximize_toggled} />
+                    { "Make targets as fast as the input limits allow" }
+                </label>
                 <p>{ status_message }</p>
+                { summary }
                 <FactorySteps>
                 {
                     for steps.iter().map(|step| {
-                        html_nested! { <FactoryStep step={step.clone()} /> }
+                        let step_name = step.factory.recipe_name();
+                        let beacons_step_name = step_name.clone();
+                        html_nested! {
+                            <FactoryStep
+                                step={step.clone()}
+                                on_modules_change={link.callback(move |modules| CalculatorMessage::ChangeStepModules(step_name.clone(), modules))}
+                                on_beacons_change={link.callback(move |beacons| CalculatorMessage::ChangeStepBeacons(beacons_step_name.clone(), beacons))}
+                                belts={belts.clone()}
+                                pipes={pipes.clone()}
+                                inserters={inserters.clone()} />
+                        }
                     })
                 }
                 </FactorySteps>
-                <h6>{ "Warning: might contain slight side-effects including but not limited to 3200 oil refineries" }</h6>
             </div>
         }
     }
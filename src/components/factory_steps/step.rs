@@ -1,13 +1,33 @@
+use std::rc::Rc;
 use yew::prelude::*;
 
-use crate::components::{CalcStep, SpriteSheetIcon};
+use crate::{
+    components::{
+        belts_label, pipes_label, pollution_label, power_label, trimmed, BeaconSetting,
+        BeaconSettingInput, BeltCounter, CalcStep, InserterCounter, Machine, ModuleSlots,
+        PipeCounter, SpriteSheetIcon,
+    },
+    constants::GAME_DATA,
+};
 
 #[derive(Debug)]
 pub struct FactoryStep;
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct FactoryStepProperties {
-    pub step: CalcStep,
+    pub step: CalcStep<'static>,
+    #[prop_or_default]
+    pub on_modules_change: Callback<Vec<String>>,
+    #[prop_or_default]
+    pub on_beacons_change: Callback<BeaconSetting>,
+    /// Products are annotated with the belts they need
+    #[prop_or_default]
+    pub belts: Option<Rc<BeltCounter<'static>>>,
+    /// Fluid products are annotated with the pipelines they need
+    #[prop_or_default]
+    pub pipes: Option<Rc<PipeCounter<'static>>>,
+    #[prop_or_default]
+    pub inserters: Option<Rc<InserterCounter<'static>>>,
 }
 
 impl Component for FactoryStep {
@@ -21,22 +41,78 @@ impl Component for FactoryStep {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let props = ctx.props();
         let famount = props.step.amount;
+        let factory = &props.step.factory;
+        let modules = if factory.module_slots() > 0 {
+            let choices: Vec<_> = GAME_DATA
+                .modules_sorted()
+                .into_iter()
+                .filter(|module| factory.accepts_module(module))
+                .collect();
+            html! {
+                <ModuleSlots
+                    slots={factory.module_slots()}
+                    selected={factory.modules().map(|m| m.names()).unwrap_or_default()}
+                    choices={choices}
+                    group={factory.recipe_name()}
+                    callback={props.on_modules_change.clone()} />
+            }
+        } else {
+            html! {}
+        };
+        let beacons = if factory.module_slots() > 0 && !GAME_DATA.beacons.is_empty() {
+            html! {
+                <BeaconSettingInput
+                    setting={factory.modules().and_then(|m| m.beacon_setting()).unwrap_or_default()}
+                    group={factory.recipe_name()}
+                    callback={props.on_beacons_change.clone()} />
+            }
+        } else {
+            html! {}
+        };
+        // Rocket launch steps count launches per minute, not silos
+        let machines = if let Machine::RocketLaunch(_, _, _) = factory.machine {
+            html! { {format!("{} rockets/min", trimmed(famount))} }
+        } else {
+            html! {
+                <>
+                {format!("{}x ", trimmed(famount))}
+                <SpriteSheetIcon prefix={factory.icon_prefix().to_string()} name={props.step.machine_name()} />
+                </>
+            }
+        };
         html! {
             <li><p>
-                {format!("{}x ", format!("{:.3}", famount).trim_end_matches('0').trim_end_matches('.'))}
-                <SpriteSheetIcon prefix={props.step.factory.icon_prefix().to_string()} name={props.step.machine_name()} />
+                { machines }
                 {" producing "}
                 {
                     for props.step.produced_per_sec().iter().map(|(name, amount)| {
                         html_nested! {
                             <>
                             <SpriteSheetIcon prefix={"item".to_string()} name={name.clone()}/>
-                            {format!("{}; ", format!("x{:.3}", amount).trim_end_matches('0').trim_end_matches('.'))}
+                            {format!("x{}", trimmed(*amount))}
+                            if factory.is_fluid(name) {
+                                { pipes_label(props.pipes.as_deref(), *amount) }
+                            } else {
+                                { belts_label(props.belts.as_deref(), name, *amount) }
+                            }
+                            {"; "}
                             </>
                         }
                     })
                 }
-            </p></li>
+                if let Some(power) = power_label(props.step.power_usage()) {
+                    {format!("power: {}", power)}
+                }
+                if let Some(pollution) = pollution_label(props.step.pollution_per_minute()) {
+                    {format!("; pollution: {}", pollution)}
+                }
+                if let Some(inserters) = &props.inserters {
+                    {format!("; inserters per machine: {}", props.step.inserters_per_machine(inserters))}
+                }
+            </p>
+            { modules }
+            { beacons }
+            </li>
         }
     }
 }
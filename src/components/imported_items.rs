@@ -0,0 +1,47 @@
+use yew::prelude::*;
+
+use super::ItemSelectDropdown;
+
+#[derive(Debug)]
+pub struct ImportedItems;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ImportedItemsProperties {
+    pub items: Vec<String>,
+    pub group: String,
+    pub on_add: Callback<()>,
+    pub on_remove: Callback<usize>,
+    pub on_change: Callback<(usize, String)>,
+}
+
+impl Component for ImportedItems {
+    type Message = ();
+    type Properties = ImportedItemsProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <ul class="imported-items">
+            {
+                for props.items.iter().enumerate().map(|(i, item)| {
+                    let on_remove = props.on_remove.reform(move |_| i);
+                    let on_change = props.on_change.reform(move |item| (i, item));
+                    html_nested! {
+                        <li class="target" key={i}>
+                            <button class="remove-item" onclick={on_remove}> {"x"} </button>
+                            <ItemSelectDropdown index={i} group={props.group.clone()} selected_item={item.clone()} callback={on_change} />
+                        </li>
+                    }
+                })
+            }
+                <li key={usize::MAX}>
+                    <button class="add-item" onclick={props.on_add.reform(|_| ())}> {"+"} </button>
+                </li>
+            </ul>
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::ItemSelectDropdown;
+
+/// Inputs supplied from outside up to a rate in items per second
+#[derive(Debug)]
+pub struct InputLimits;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct InputLimitsProperties {
+    pub limits: Vec<(String, f64)>,
+    pub on_add: Callback<()>,
+    pub on_remove: Callback<usize>,
+    pub on_change: Callback<(usize, (String, f64))>,
+}
+
+impl Component for InputLimits {
+    type Message = ();
+    type Properties = InputLimitsProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <ul class="input-limits">
+            {
+                for props.limits.iter().cloned().enumerate().map(|(i, (item, rate))| {
+                    let on_remove = props.on_remove.reform(move |_| i);
+                    let on_item_change = props.on_change.reform(move |item| (i, (item, rate)));
+                    let limited_item = item.clone();
+                    let on_change = props.on_change.clone();
+                    let on_rate_change = Callback::from(move |e: Event| {
+                        if let Some(rate) = e
+                            .target()
+                            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                            .and_then(|i| i.value().parse().ok())
+                        {
+                            on_change.emit((i, (limited_item.clone(), rate)))
+                        }
+                    });
+                    html_nested! {
+                        <li class="target" key={i}>
+                            <button class="remove-item" onclick={on_remove}> {"x"} </button>
+                            <ItemSelectDropdown index={i} group="limit" selected_item={item} callback={on_item_change} />
+                            {"items/s: "}
+                            <input type="text" onchange={on_rate_change} value={rate.to_string()} />
+                        </li>
+                    }
+                })
+            }
+                <li key={usize::MAX}>
+                    <button class="add-item" onclick={props.on_add.reform(|_| ())}> {"+"} </button>
+                </li>
+            </ul>
+        }
+    }
+}
@@ -16,10 +16,17 @@ pub struct ItemSelectDropdown {
 pub struct ItemSelectDropdownProperties {
     pub callback: Callback<String>,
     pub index: usize,
+    // Dropdowns from different lists must not share input names
+    #[prop_or_else(default_group)]
+    pub group: String,
     #[prop_or_else(default_item)]
     pub selected_item: String,
 }
 
+fn default_group() -> String {
+    "target".into()
+}
+
 fn default_item() -> String {
     DEFAULT_ITEM.into()
 }
@@ -93,14 +100,14 @@ impl Component for ItemSelectDropdown {
                                     html_nested!{
                                         <>
                                         {for subgroup.iter().enumerate().map(|(i_3, item)| {
-                                            let input_id = format!("input-{}-{}-{}-{}", props.index, i_1, i_2, i_3);
+                                            let input_id = format!("input-{}-{}-{}-{}-{}", props.group, props.index, i_1, i_2, i_3);
                                             html_nested! {
                                                 <span>
                                                 <input
                                                     type="radio"
                                                     value={item.name.clone()}
                                                     onchange={on_item_selected.clone()}
-                                                    name={format!("item-select-{}", props.index)}
+                                                    name={format!("item-select-{}-{}", props.group, props.index)}
                                                     checked={ item.name == self.selected_item }
                                                     id={input_id.clone()}
                                                 />
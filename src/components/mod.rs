@@ -1,11 +1,29 @@
+mod beacon_setting_input;
+mod calculation_summary;
 mod calculator;
 mod factory_steps;
+mod imported_items;
+mod input_limits;
 mod item_select_dropdown;
+mod module_slots;
+mod number_format;
+mod research_targets;
+mod robot_estimate;
 mod spritesheet;
+mod train_planner;
 mod usersettings;
 
+pub use beacon_setting_input::*;
+pub use calculation_summary::*;
 pub use calculator::*;
 pub use factory_steps::*;
+pub use imported_items::*;
+pub use input_limits::*;
 pub use item_select_dropdown::*;
+pub use module_slots::*;
+pub use number_format::*;
+pub use research_targets::*;
+pub use robot_estimate::*;
 pub use spritesheet::*;
+pub use train_planner::*;
 pub use usersettings::*;
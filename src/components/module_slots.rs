@@ -0,0 +1,78 @@
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+use super::ItemIcon;
+use crate::data::Module;
+
+#[derive(Debug)]
+pub struct ModuleSlots;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ModuleSlotsProperties {
+    pub slots: usize,
+    pub selected: Vec<String>,
+    pub choices: Vec<&'static Module>,
+    // Radio buttons of different slot lists must not share input names
+    pub group: String,
+    pub callback: Callback<Vec<String>>,
+}
+
+impl Component for ModuleSlots {
+    type Message = (usize, String);
+    type Properties = ModuleSlotsProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let (slot, module) = msg;
+        let mut selected = props.selected.clone();
+        selected.resize(props.slots, String::new());
+        selected[slot] = module;
+        // Empty slots are stored as empty names
+        props
+            .callback
+            .emit(selected.into_iter().filter(|m| !m.is_empty()).collect());
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <div class="module-slots">
+            {
+                for (0..props.slots).map(|slot| {
+                    let selected = props.selected.get(slot).cloned().unwrap_or_default();
+                    let on_selected = ctx.link().batch_callback(move |e: Event| {
+                        let target: Option<EventTarget> = e.target();
+                        let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+                        input.map(|i| (slot, i.value()))
+                    });
+                    let name = format!("module-slot-{}-{}", props.group, slot);
+                    html_nested! {
+                        <div class="module-slot">
+                            <label>
+                                <input type="radio" name={name.clone()} value="" checked={selected.is_empty()} onchange={on_selected.clone()}/>
+                                {"none"}
+                            </label>
+                            {
+                                for props.choices.iter().map(|module| {
+                                    html_nested! {
+                                        <label>
+                                            <input type="radio" name={name.clone()} value={module.name.clone()} checked={selected == module.name} onchange={on_selected.clone()}/>
+                                            <ItemIcon item={module.name.clone()} />
+                                        </label>
+                                    }
+                                })
+                            }
+                        </div>
+                    }
+                })
+            }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,7 @@
+/// Number with at most three decimals and no trailing zeroes
+pub fn trimmed(value: f64) -> String {
+    format!("{:.3}", value)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
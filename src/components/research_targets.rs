@@ -0,0 +1,185 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::{ItemIcon, ResearchTarget};
+use crate::constants::GAME_DATA;
+
+#[derive(Debug)]
+pub struct ResearchTargets;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct ResearchTargetsProperties {
+    pub targets: Vec<ResearchTarget>,
+    pub on_add: Callback<()>,
+    pub on_remove: Callback<usize>,
+    pub on_change: Callback<(usize, ResearchTarget)>,
+}
+
+impl ResearchTargets {
+    fn input_element(e: Event) -> Option<HtmlInputElement> {
+        e.target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+    }
+
+    fn target_view(&self, ctx: &Context<Self>, idx: usize, target: &ResearchTarget) -> Html {
+        let props = ctx.props();
+        let on_change = props.on_change.clone();
+        let emit = move |target: ResearchTarget| on_change.emit((idx, target));
+
+        let mode_name = format!("research-mode-{}", idx);
+        let (labs_mode, spm_mode) = {
+            let emit = emit.clone();
+            let emit_spm = emit.clone();
+            (
+                Callback::from(move |_: Event| {
+                    emit(ResearchTarget::Labs {
+                        technology: GAME_DATA
+                            .technologies_sorted()
+                            .first()
+                            .map(|t| t.name.clone())
+                            .unwrap_or_default(),
+                        labs: 1.0,
+                    })
+                }),
+                Callback::from(move |_: Event| {
+                    emit_spm(ResearchTarget::science_per_minute(&GAME_DATA, 60.0))
+                }),
+            )
+        };
+
+        let details = match target {
+            ResearchTarget::Labs { technology, labs } => {
+                let labs = *labs;
+                let on_technology_change = {
+                    let emit = emit.clone();
+                    Callback::from(move |e: Event| {
+                        if let Some(input) = Self::input_element(e) {
+                            emit(ResearchTarget::Labs {
+                                technology: input.value(),
+                                labs,
+                            })
+                        }
+                    })
+                };
+                let technology_name = technology.clone();
+                let on_labs_change = Callback::from(move |e: Event| {
+                    if let Some(labs) = Self::input_element(e).and_then(|i| i.value().parse().ok())
+                    {
+                        emit(ResearchTarget::Labs {
+                            technology: technology_name.clone(),
+                            labs,
+                        })
+                    }
+                });
+                html! {
+                    <>
+                    {"Technology: "}
+                    <input type="text" list="technologies" onchange={on_technology_change} value={technology.clone()} />
+                    {"Labs: "}
+                    <input type="text" onchange={on_labs_change} value={labs.to_string()} />
+                    </>
+                }
+            }
+            ResearchTarget::SciencePerMinute { science_packs, spm } => {
+                let spm = *spm;
+                let selected = science_packs.clone();
+                let on_spm_change = {
+                    let emit = emit.clone();
+                    let science_packs = science_packs.clone();
+                    Callback::from(move |e: Event| {
+                        if let Some(spm) =
+                            Self::input_element(e).and_then(|i| i.value().parse().ok())
+                        {
+                            emit(ResearchTarget::SciencePerMinute {
+                                science_packs: science_packs.clone(),
+                                spm,
+                            })
+                        }
+                    })
+                };
+                html! {
+                    <>
+                    {
+                        for GAME_DATA.science_packs().into_iter().map(|pack| {
+                            let emit = emit.clone();
+                            let selected_packs = selected.clone();
+                            let toggled_pack = pack.clone();
+                            let on_toggled = Callback::from(move |e: Event| {
+                                if let Some(input) = Self::input_element(e) {
+                                    // Keep the order of the labs inputs
+                                    let science_packs = GAME_DATA
+                                        .science_packs()
+                                        .into_iter()
+                                        .filter(|p| {
+                                            if *p == toggled_pack {
+                                                input.checked()
+                                            } else {
+                                                selected_packs.contains(p)
+                                            }
+                                        })
+                                        .collect();
+                                    emit(ResearchTarget::SciencePerMinute { science_packs, spm })
+                                }
+                            });
+                            html_nested! {
+                                <label>
+                                    <input type="checkbox" checked={selected.contains(&pack)} onchange={on_toggled} />
+                                    <ItemIcon item={pack.clone()} />
+                                </label>
+                            }
+                        })
+                    }
+                    {"SPM: "}
+                    <input type="text" onchange={on_spm_change} value={spm.to_string()} />
+                    </>
+                }
+            }
+        };
+
+        html! {
+            <li class="target" key={idx}>
+                <button class="remove-item" onclick={props.on_remove.reform(move |_| idx)}> {"x"} </button>
+                <label>
+                    <input type="radio" name={mode_name.clone()} checked={matches!(target, ResearchTarget::Labs { .. })} onchange={labs_mode} />
+                    {"labs"}
+                </label>
+                <label>
+                    <input type="radio" name={mode_name} checked={matches!(target, ResearchTarget::SciencePerMinute { .. })} onchange={spm_mode} />
+                    {"science per minute"}
+                </label>
+                { details }
+            </li>
+        }
+    }
+}
+
+impl Component for ResearchTargets {
+    type Message = ();
+    type Properties = ResearchTargetsProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <div class="research-targets">
+                <datalist id="technologies">
+                {
+                    for GAME_DATA.technologies_sorted().into_iter().map(|technology| {
+                        html_nested! { <option value={technology.name.clone()} /> }
+                    })
+                }
+                </datalist>
+                <ul>
+                    { for props.targets.iter().enumerate().map(|(idx, target)| self.target_view(ctx, idx, target)) }
+                    <li key={usize::MAX}>
+                        <button class="add-item" onclick={props.on_add.reform(|_| ())}> {"+"} </button>
+                    </li>
+                </ul>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::{trimmed, ItemIcon, RobotSetting};
+
+/// Logistic robots needed for every item flow of a calculation
+#[derive(Debug)]
+pub struct RobotEstimate;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct RobotEstimateProperties {
+    pub robots: Vec<(String, f64)>,
+    pub setting: RobotSetting,
+    pub on_change: Callback<RobotSetting>,
+}
+
+impl RobotEstimate {
+    fn setting_input<T: ToString + std::str::FromStr + 'static>(
+        label: &str,
+        value: T,
+        props: &RobotEstimateProperties,
+        apply: fn(&mut RobotSetting, T),
+    ) -> Html {
+        let setting = props.setting.clone();
+        let on_change = props.on_change.clone();
+        let onchange = Callback::from(move |e: Event| {
+            if let Some(value) = e
+                .target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|i| i.value().parse().ok())
+            {
+                let mut setting = setting.clone();
+                apply(&mut setting, value);
+                on_change.emit(setting)
+            }
+        });
+        html! {
+            <label>
+                { label }
+                <input type="text" {onchange} value={value.to_string()} />
+            </label>
+        }
+    }
+}
+
+impl Component for RobotEstimate {
+    type Message = ();
+    type Properties = RobotEstimateProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let total: f64 = props.robots.iter().map(|(_, robots)| robots).sum();
+        html! {
+            <div class="summary-list robot-estimate">
+                <p>{ "Logistic robots:" }</p>
+                { Self::setting_input("Average distance, tiles: ", props.setting.distance, props, |s, v| s.distance = v) }
+                { Self::setting_input("Speed research level: ", props.setting.speed_level, props, |s, v| s.speed_level = v) }
+                { Self::setting_input("Cargo size research level: ", props.setting.cargo_level, props, |s, v| s.cargo_level = v) }
+                <ul>
+                {
+                    for props.robots.iter().map(|(name, robots)| {
+                        html_nested! {
+                            <li>
+                                <ItemIcon item={name.clone()} />
+                                {format!("{} robots", trimmed(*robots))}
+                            </li>
+                        }
+                    })
+                }
+                </ul>
+                <p>{ format!("Total: {} robots", trimmed(total)) }</p>
+            </div>
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod item_icon;
+mod recipe_icon;
 
 pub use item_icon::*;
+pub use recipe_icon::*;
 
 use crate::constants::{
     DOWNSCALE, ICON_MAP, ICON_SIZE, ORIGINAL_ICON_SIZE, ORIGINAL_SPRITESHEET_SIZE,
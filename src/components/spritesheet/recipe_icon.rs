@@ -0,0 +1,43 @@
+use yew::prelude::*;
+
+use super::SpriteSheetIcon;
+use crate::{
+    constants::ICON_MAP,
+    data::{Difficulty, Recipe},
+};
+
+#[derive(Debug, Clone)]
+pub struct RecipeIcon;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct RecipeIconProperties {
+    pub recipe: &'static Recipe,
+}
+
+impl Component for RecipeIcon {
+    type Message = ();
+    type Properties = RecipeIconProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let recipe = ctx.props().recipe;
+        // Recipes without their own icon in the spritesheet use the icon of their main product
+        if recipe.icon.is_some() && ICON_MAP.contains_key(&format!("recipe-{}", recipe.name)) {
+            html! {
+                <SpriteSheetIcon prefix={"recipe"} name={recipe.name.clone()} />
+            }
+        } else {
+            let product = recipe
+                .produces(Difficulty::default())
+                .first()
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default();
+            html! {
+                <SpriteSheetIcon prefix={"item"} name={product} />
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::{trimmed, ItemIcon, TrainCounter};
+
+/// Wagons and trains needed to bring in the inputs of a calculation
+#[derive(Debug)]
+pub struct TrainPlanner;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct TrainPlannerProperties {
+    pub inputs: Vec<(String, f64)>,
+    pub trains: Rc<TrainCounter<'static>>,
+    /// Round trip time in seconds
+    pub trip_time: f64,
+    pub wagons: u32,
+    pub on_trip_time_change: Callback<f64>,
+    pub on_wagons_change: Callback<u32>,
+}
+
+fn parsed_input<T: std::str::FromStr>(e: Event) -> Option<T> {
+    e.target()
+        .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+        .and_then(|i| i.value().parse().ok())
+}
+
+impl Component for TrainPlanner {
+    type Message = ();
+    type Properties = TrainPlannerProperties;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let estimates: Vec<_> = props
+            .inputs
+            .iter()
+            .filter_map(|(name, rate)| {
+                let estimate = props
+                    .trains
+                    .estimate(name, *rate, props.trip_time, props.wagons)?;
+                Some((name.clone(), estimate))
+            })
+            .collect();
+        if estimates.is_empty() {
+            return html! {};
+        }
+        let on_trip_time_change = props.on_trip_time_change.clone();
+        let on_trip_time_change = Callback::from(move |e: Event| {
+            if let Some(trip_time) = parsed_input(e).filter(|t: &f64| *t > 0.0) {
+                on_trip_time_change.emit(trip_time)
+            }
+        });
+        let on_wagons_change = props.on_wagons_change.clone();
+        let on_wagons_change = Callback::from(move |e: Event| {
+            if let Some(wagons) = parsed_input(e).filter(|w: &u32| *w > 0) {
+                on_wagons_change.emit(wagons)
+            }
+        });
+        html! {
+            <div class="summary-list train-planner">
+                <p>{ "Trains for inputs:" }</p>
+                <label>
+                    { "Round trip time, s: " }
+                    <input type="text" onchange={on_trip_time_change} value={props.trip_time.to_string()} />
+                </label>
+                <label>
+                    { "Wagons per train: " }
+                    <input type="text" onchange={on_wagons_change} value={props.wagons.to_string()} />
+                </label>
+                <ul>
+                {
+                    for estimates.into_iter().map(|(name, estimate)| {
+                        html_nested! {
+                            <li>
+                                <ItemIcon item={name} />
+                                {format!(
+                                    "{} wagons/m, {} trains",
+                                    trimmed(estimate.wagons_per_minute),
+                                    trimmed(estimate.trains)
+                                )}
+                            </li>
+                        }
+                    })
+                }
+                </ul>
+            </div>
+        }
+    }
+}
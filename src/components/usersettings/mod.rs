@@ -1,26 +1,48 @@
 mod raw_user_settings;
+mod user_setting_disabled_recipe;
+mod user_setting_item_recipe;
 mod user_setting_recipe_category;
 mod user_setting_resource_category;
 mod user_settings_page;
 
 pub use raw_user_settings::*;
+pub use user_setting_disabled_recipe::*;
+pub use user_setting_item_recipe::*;
 pub use user_setting_recipe_category::*;
 pub use user_setting_resource_category::*;
 pub use user_settings_page::*;
 
-use self::raw_user_settings::RawUserSettings;
+use self::raw_user_settings::{raw_modules, RawUserSettings};
 use crate::{
-    constants::GAME_DATA,
+    components::{BeaconSetting, SolverSettings},
+    constants::{DEFAULT_BELT, DEFAULT_FUEL, DEFAULT_GENERATOR, DEFAULT_INSERTER, GAME_DATA},
     data::*,
-    prototype_ref::{AssemblingMachineRef, MiningDrillRef},
+    prototype_ref::{AssemblingMachineRef, MiningDrillRef, ModuleRef, RecipeRef},
+    solver::DEFAULT_DISABLED_RECIPES,
 };
 use gloo_storage::Storage;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq)]
 pub struct UserSettings {
     recipe_category_prefs: HashMap<String, AssemblingMachineRef>,
     resource_category_prefs: HashMap<String, MiningDrillRef>,
+    item_recipe_prefs: HashMap<String, RecipeRef>,
+    disabled_recipes: HashSet<RecipeRef>,
+    imported_items: Vec<String>,
+    recipe_category_modules: HashMap<String, Vec<ModuleRef>>,
+    resource_category_modules: HashMap<String, Vec<ModuleRef>>,
+    recipe_category_beacons: HashMap<String, BeaconSetting>,
+    difficulty: Difficulty,
+    fuel: String,
+    belt: String,
+    inserter: String,
+    inserter_stack_bonus: u32,
+    stack_inserter_bonus: u32,
+    mining_productivity_level: u32,
+    resource_yields: HashMap<String, f64>,
+    water_source_prefs: HashMap<String, String>,
+    generator: String,
 }
 
 impl UserSettings {
@@ -32,6 +54,126 @@ impl UserSettings {
         self.resource_category_prefs.get(category)
     }
 
+    pub fn recipe(&self, item: &str) -> Option<&RecipeRef> {
+        self.item_recipe_prefs.get(item)
+    }
+
+    pub fn is_recipe_disabled(&self, recipe: &str) -> bool {
+        self.disabled_recipes
+            .contains(&RecipeRef::new(recipe.to_string()))
+    }
+
+    pub fn imported_items(&self) -> &[String] {
+        &self.imported_items
+    }
+
+    pub fn recipe_category_modules(&self, category: &str) -> &[ModuleRef] {
+        self.recipe_category_modules
+            .get(category)
+            .map_or(&[], |modules| modules.as_slice())
+    }
+
+    pub fn resource_category_modules(&self, category: &str) -> &[ModuleRef] {
+        self.resource_category_modules
+            .get(category)
+            .map_or(&[], |modules| modules.as_slice())
+    }
+
+    pub fn recipe_category_beacons(&self, category: &str) -> Option<&BeaconSetting> {
+        self.recipe_category_beacons.get(category)
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Fuel burnt by machines with burner energy sources
+    pub fn fuel(&self) -> &str {
+        &self.fuel
+    }
+
+    /// Belt tier used to count belts for item flows
+    pub fn belt(&self) -> &str {
+        &self.belt
+    }
+
+    /// Inserter used to count inserters of machines
+    pub fn inserter(&self) -> &str {
+        &self.inserter
+    }
+
+    /// Extra items carried by inserters other than stack inserters in each swing
+    pub fn inserter_stack_bonus(&self) -> u32 {
+        self.inserter_stack_bonus
+    }
+
+    /// Extra items carried by stack inserters in each swing
+    pub fn stack_inserter_bonus(&self) -> u32 {
+        self.stack_inserter_bonus
+    }
+
+    /// Offshore pump or recipe supplying a fluid
+    pub fn water_source(&self, fluid: &str) -> Option<&str> {
+        self.water_source_prefs.get(fluid).map(String::as_str)
+    }
+
+    /// Generator making electricity
+    pub fn generator(&self) -> &str {
+        &self.generator
+    }
+
+    pub fn mining_productivity_level(&self) -> u32 {
+        self.mining_productivity_level
+    }
+
+    /// Yield of an infinite resource in percent
+    pub fn resource_yield(&self, resource: &str) -> f64 {
+        self.resource_yields.get(resource).copied().unwrap_or(100.0)
+    }
+
+    /// Preferences in the form used by the solver
+    pub fn solver_settings(&self) -> SolverSettings {
+        SolverSettings {
+            difficulty: self.difficulty,
+            assembling_machines: self
+                .recipe_category_prefs
+                .iter()
+                .map(|(cat, am)| (cat.clone(), am.get_name().to_string()))
+                .collect(),
+            mining_drills: self
+                .resource_category_prefs
+                .iter()
+                .map(|(cat, md)| (cat.clone(), md.get_name().to_string()))
+                .collect(),
+            item_recipes: self
+                .item_recipe_prefs
+                .iter()
+                .map(|(item, rec)| (item.clone(), rec.get_name().to_string()))
+                .collect(),
+            disabled_recipes: self
+                .disabled_recipes
+                .iter()
+                .map(|rec| rec.get_name().to_string())
+                .collect(),
+            recipe_category_modules: raw_modules(&self.recipe_category_modules),
+            resource_category_modules: raw_modules(&self.resource_category_modules),
+            recipe_category_beacons: self.recipe_category_beacons.clone(),
+            fuel: Some(self.fuel.clone()),
+            water_sources: self
+                .water_source_prefs
+                .iter()
+                .map(|(fluid, source)| (fluid.clone(), source.clone()))
+                .collect(),
+            generator: Some(self.generator.clone()),
+            mining_productivity_level: self.mining_productivity_level,
+            resource_yields: self
+                .resource_yields
+                .iter()
+                .map(|(resource, percent)| (resource.clone(), *percent))
+                .collect(),
+        }
+    }
+
     pub fn change_recipe_category(&mut self, category: &str, machine: AssemblingMachineRef) {
         log::info!(
             "Changed assembler for category {} to {}",
@@ -54,6 +196,113 @@ impl UserSettings {
         self.write()
     }
 
+    pub fn change_item_recipe(&mut self, item: &str, recipe: RecipeRef) {
+        log::info!("Changed recipe for item {} to {}", item, recipe.get_name());
+        self.item_recipe_prefs.insert(item.to_string(), recipe);
+        self.write()
+    }
+
+    pub fn change_recipe_disabled(&mut self, recipe: RecipeRef, disabled: bool) {
+        log::info!(
+            "Changed recipe {} to be {}",
+            recipe.get_name(),
+            if disabled { "disabled" } else { "enabled" }
+        );
+        if disabled {
+            self.disabled_recipes.insert(recipe);
+        } else {
+            self.disabled_recipes.remove(&recipe);
+        }
+        self.write()
+    }
+
+    pub fn change_imported_items(&mut self, items: Vec<String>) {
+        log::info!("Changed imported items to {:?}", items);
+        self.imported_items = items;
+        self.write()
+    }
+
+    pub fn change_recipe_category_modules(&mut self, category: &str, modules: Vec<ModuleRef>) {
+        log::info!("Changed modules for recipe category {}", category);
+        self.recipe_category_modules
+            .insert(category.to_string(), modules);
+        self.write()
+    }
+
+    pub fn change_resource_category_modules(&mut self, category: &str, modules: Vec<ModuleRef>) {
+        log::info!("Changed modules for resource category {}", category);
+        self.resource_category_modules
+            .insert(category.to_string(), modules);
+        self.write()
+    }
+
+    pub fn change_recipe_category_beacons(&mut self, category: &str, beacons: BeaconSetting) {
+        log::info!("Changed beacons for recipe category {}", category);
+        self.recipe_category_beacons
+            .insert(category.to_string(), beacons);
+        self.write()
+    }
+
+    pub fn change_difficulty(&mut self, difficulty: Difficulty) {
+        log::info!("Changed difficulty to {:?}", difficulty);
+        self.difficulty = difficulty;
+        self.write()
+    }
+
+    pub fn change_fuel(&mut self, fuel: String) {
+        log::info!("Changed fuel to {}", fuel);
+        self.fuel = fuel;
+        self.write()
+    }
+
+    pub fn change_belt(&mut self, belt: String) {
+        log::info!("Changed belt to {}", belt);
+        self.belt = belt;
+        self.write()
+    }
+
+    pub fn change_inserter(&mut self, inserter: String) {
+        log::info!("Changed inserter to {}", inserter);
+        self.inserter = inserter;
+        self.write()
+    }
+
+    pub fn change_water_source(&mut self, fluid: String, source: String) {
+        log::info!("Changed water source for {} to {}", fluid, source);
+        self.water_source_prefs.insert(fluid, source);
+        self.write()
+    }
+
+    pub fn change_generator(&mut self, generator: String) {
+        log::info!("Changed generator to {}", generator);
+        self.generator = generator;
+        self.write()
+    }
+
+    pub fn change_mining_productivity_level(&mut self, level: u32) {
+        log::info!("Changed mining productivity level to {}", level);
+        self.mining_productivity_level = level;
+        self.write()
+    }
+
+    pub fn change_resource_yield(&mut self, resource: String, percent: f64) {
+        log::info!("Changed yield of {} to {}%", resource, percent);
+        self.resource_yields.insert(resource, percent);
+        self.write()
+    }
+
+    pub fn change_inserter_stack_bonus(&mut self, stack_bonus: u32) {
+        log::info!("Changed inserter stack bonus to {}", stack_bonus);
+        self.inserter_stack_bonus = stack_bonus;
+        self.write()
+    }
+
+    pub fn change_stack_inserter_bonus(&mut self, stack_bonus: u32) {
+        log::info!("Changed stack inserter bonus to {}", stack_bonus);
+        self.stack_inserter_bonus = stack_bonus;
+        self.write()
+    }
+
     fn write(&self) {
         gloo_storage::LocalStorage::set("user_settings", RawUserSettings::from(self)).unwrap();
     }
@@ -87,9 +336,38 @@ impl UserSettings {
                 MiningDrillRef::new(mining_drills[0].name.clone()),
             );
         }
+        let disabled_recipes: HashSet<RecipeRef> = DEFAULT_DISABLED_RECIPES
+            .iter()
+            .map(|name| RecipeRef::new(name.to_string()))
+            .collect();
+        let mut item_recipe_prefs = HashMap::new();
+        for (item, recipes) in game_data.items_with_multiple_recipes(Difficulty::default()) {
+            let recipe = recipes
+                .iter()
+                .find(|recipe| !disabled_recipes.contains(&RecipeRef::new(recipe.name.clone())))
+                .unwrap_or(&recipes[0]);
+            log::info!("recipe for item {}: {}", item, recipe.name);
+            item_recipe_prefs.insert(item, RecipeRef::new(recipe.name.clone()));
+        }
         let result = Self {
             recipe_category_prefs,
             resource_category_prefs,
+            item_recipe_prefs,
+            disabled_recipes,
+            imported_items: Vec::new(),
+            recipe_category_modules: HashMap::new(),
+            resource_category_modules: HashMap::new(),
+            recipe_category_beacons: HashMap::new(),
+            difficulty: Difficulty::default(),
+            fuel: DEFAULT_FUEL.into(),
+            belt: DEFAULT_BELT.into(),
+            inserter: DEFAULT_INSERTER.into(),
+            inserter_stack_bonus: 0,
+            stack_inserter_bonus: 0,
+            mining_productivity_level: 0,
+            resource_yields: HashMap::new(),
+            water_source_prefs: HashMap::new(),
+            generator: DEFAULT_GENERATOR.into(),
         };
         result.write();
         result
@@ -124,9 +402,122 @@ impl UserSettings {
                     (cat, md_ref)
                 })
                 .collect(),
+            item_recipe_prefs: raw_us
+                .item_recipe_prefs
+                .into_iter()
+                .map(|(item, rec)| {
+                    let rec_ref = if let Some(rec_gd) = game_data.recipes.get(&rec) {
+                        RecipeRef::new(rec_gd.name.clone())
+                    } else {
+                        log::warn!("Recipe {} not found in game data", rec);
+                        RecipeRef::new(rec)
+                    };
+                    (item, rec_ref)
+                })
+                .collect(),
+            disabled_recipes: raw_us
+                .disabled_recipes
+                .into_iter()
+                .map(RecipeRef::new)
+                .collect(),
+            imported_items: raw_us.imported_items,
+            recipe_category_modules: Self::modules_from_raw(
+                raw_us.recipe_category_modules,
+                game_data,
+            ),
+            resource_category_modules: Self::modules_from_raw(
+                raw_us.resource_category_modules,
+                game_data,
+            ),
+            recipe_category_beacons: raw_us
+                .recipe_category_beacons
+                .into_iter()
+                .filter(|(_, setting)| {
+                    let found = setting.beacon.is_empty()
+                        || game_data.beacons.contains_key(&setting.beacon);
+                    if !found {
+                        log::warn!("Beacon {} not found in game data", setting.beacon);
+                    }
+                    found
+                })
+                .collect(),
+            difficulty: raw_us.difficulty,
+            fuel: if game_data.items.contains_key(&raw_us.fuel) {
+                raw_us.fuel
+            } else {
+                log::warn!("Fuel {} not found in game data", raw_us.fuel);
+                DEFAULT_FUEL.into()
+            },
+            belt: if game_data.transport_belts.contains_key(&raw_us.belt) {
+                raw_us.belt
+            } else {
+                log::warn!("Belt {} not found in game data", raw_us.belt);
+                DEFAULT_BELT.into()
+            },
+            inserter: if game_data.inserters.contains_key(&raw_us.inserter) {
+                raw_us.inserter
+            } else {
+                log::warn!("Inserter {} not found in game data", raw_us.inserter);
+                DEFAULT_INSERTER.into()
+            },
+            inserter_stack_bonus: raw_us.inserter_stack_bonus,
+            stack_inserter_bonus: raw_us.stack_inserter_bonus,
+            water_source_prefs: raw_us
+                .water_source_prefs
+                .into_iter()
+                .filter(|(_, source)| {
+                    let found = game_data.offshore_pumps.contains_key(source)
+                        || game_data.recipes.contains_key(source);
+                    if !found {
+                        log::warn!("Water source {} not found in game data", source);
+                    }
+                    found
+                })
+                .collect(),
+            generator: if game_data.generators.contains_key(&raw_us.generator) {
+                raw_us.generator
+            } else {
+                log::warn!("Generator {} not found in game data", raw_us.generator);
+                DEFAULT_GENERATOR.into()
+            },
+            mining_productivity_level: raw_us.mining_productivity_level,
+            resource_yields: raw_us
+                .resource_yields
+                .into_iter()
+                .filter(|(resource, _)| {
+                    let found = game_data.resources.contains_key(resource);
+                    if !found {
+                        log::warn!("Resource {} not found in game data", resource);
+                    }
+                    found
+                })
+                .collect(),
         }
     }
 
+    fn modules_from_raw(
+        raw_modules: HashMap<String, Vec<String>>,
+        game_data: &GameData,
+    ) -> HashMap<String, Vec<ModuleRef>> {
+        raw_modules
+            .into_iter()
+            .map(|(cat, modules)| {
+                let modules = modules
+                    .into_iter()
+                    .filter(|module| {
+                        let found = game_data.modules.contains_key(module);
+                        if !found {
+                            log::warn!("Module {} not found in game data", module);
+                        }
+                        found
+                    })
+                    .map(ModuleRef::new)
+                    .collect();
+                (cat, modules)
+            })
+            .collect()
+    }
+
     pub fn create(game_data: &GameData) -> Self {
         if let Ok(us) = gloo_storage::LocalStorage::get("user_settings") {
             Self::from_raw(us, game_data)
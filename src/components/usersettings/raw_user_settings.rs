@@ -1,4 +1,11 @@
-use hashbrown::HashMap;
+use crate::{
+    components::BeaconSetting,
+    constants::{DEFAULT_BELT, DEFAULT_FUEL, DEFAULT_GENERATOR, DEFAULT_INSERTER},
+    data::Difficulty,
+    prototype_ref::ModuleRef,
+    solver::DEFAULT_DISABLED_RECIPES,
+};
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 
 use super::UserSettings;
@@ -7,6 +14,61 @@ use super::UserSettings;
 pub struct RawUserSettings {
     pub recipe_category_prefs: HashMap<String, String>,
     pub resource_category_prefs: HashMap<String, String>,
+    #[serde(default)]
+    pub item_recipe_prefs: HashMap<String, String>,
+    #[serde(default = "default_disabled_recipes")]
+    pub disabled_recipes: HashSet<String>,
+    #[serde(default)]
+    pub imported_items: Vec<String>,
+    #[serde(default)]
+    pub recipe_category_modules: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub resource_category_modules: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub recipe_category_beacons: HashMap<String, BeaconSetting>,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default = "default_fuel")]
+    pub fuel: String,
+    #[serde(default = "default_belt")]
+    pub belt: String,
+    #[serde(default = "default_inserter")]
+    pub inserter: String,
+    #[serde(default)]
+    pub inserter_stack_bonus: u32,
+    #[serde(default)]
+    pub stack_inserter_bonus: u32,
+    #[serde(default)]
+    pub water_source_prefs: HashMap<String, String>,
+    #[serde(default = "default_generator")]
+    pub generator: String,
+    #[serde(default)]
+    pub mining_productivity_level: u32,
+    #[serde(default)]
+    pub resource_yields: HashMap<String, f64>,
+}
+
+fn default_disabled_recipes() -> HashSet<String> {
+    DEFAULT_DISABLED_RECIPES
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn default_fuel() -> String {
+    DEFAULT_FUEL.into()
+}
+
+fn default_belt() -> String {
+    DEFAULT_BELT.into()
+}
+
+fn default_inserter() -> String {
+    DEFAULT_INSERTER.into()
+}
+
+fn default_generator() -> String {
+    DEFAULT_GENERATOR.into()
 }
 
 impl From<&UserSettings> for RawUserSettings {
@@ -15,13 +77,51 @@ impl From<&UserSettings> for RawUserSettings {
             recipe_category_prefs: us
                 .recipe_category_prefs
                 .iter()
-                .map(|(cat, am)| (cat.clone(), am.name.clone()))
+                .map(|(cat, am)| (cat.clone(), am.get_name().to_string()))
                 .collect(),
             resource_category_prefs: us
                 .resource_category_prefs
                 .iter()
-                .map(|(cat, md)| (cat.clone(), md.name.clone()))
+                .map(|(cat, md)| (cat.clone(), md.get_name().to_string()))
+                .collect(),
+            item_recipe_prefs: us
+                .item_recipe_prefs
+                .iter()
+                .map(|(item, rec)| (item.clone(), rec.get_name().to_string()))
                 .collect(),
+            disabled_recipes: us
+                .disabled_recipes
+                .iter()
+                .map(|rec| rec.get_name().to_string())
+                .collect(),
+            imported_items: us.imported_items.clone(),
+            recipe_category_modules: raw_modules(&us.recipe_category_modules),
+            resource_category_modules: raw_modules(&us.resource_category_modules),
+            recipe_category_beacons: us.recipe_category_beacons.clone(),
+            difficulty: us.difficulty,
+            fuel: us.fuel.clone(),
+            belt: us.belt.clone(),
+            inserter: us.inserter.clone(),
+            inserter_stack_bonus: us.inserter_stack_bonus,
+            stack_inserter_bonus: us.stack_inserter_bonus,
+            water_source_prefs: us.water_source_prefs.clone(),
+            generator: us.generator.clone(),
+            mining_productivity_level: us.mining_productivity_level,
+            resource_yields: us.resource_yields.clone(),
         }
     }
 }
+
+pub(super) fn raw_modules(
+    modules: &HashMap<String, Vec<ModuleRef>>,
+) -> HashMap<String, Vec<String>> {
+    modules
+        .iter()
+        .map(|(cat, modules)| {
+            (
+                cat.clone(),
+                modules.iter().map(|m| m.get_name().to_string()).collect(),
+            )
+        })
+        .collect()
+}
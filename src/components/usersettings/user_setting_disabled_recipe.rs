@@ -0,0 +1,85 @@
+use super::user_settings_page::UserSettingsPageMessage;
+use crate::{components::RecipeIcon, data::*, WrappedUserSettings};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+#[derive(Debug)]
+pub struct UserSettingDisabledRecipe {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct UserSettingDisabledRecipeProperties {
+    pub recipe: &'static Recipe,
+    pub callback: Callback<UserSettingsPageMessage>,
+}
+
+#[derive(Debug)]
+pub enum UserSettingDisabledRecipeMessage {
+    Toggled(bool),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
+}
+
+impl Component for UserSettingDisabledRecipe {
+    type Properties = UserSettingDisabledRecipeProperties;
+    type Message = UserSettingDisabledRecipeMessage;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(UserSettingDisabledRecipeMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
+
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            UserSettingDisabledRecipeMessage::Toggled(disabled) => {
+                let props = ctx.props();
+                props
+                    .callback
+                    .emit(UserSettingsPageMessage::ChangeRecipeDisabled(
+                        props.recipe,
+                        disabled,
+                    ));
+                false
+            }
+            UserSettingDisabledRecipeMessage::UserSettingsUpdated(user_settings) => {
+                self.user_settings = user_settings;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let recipe = props.recipe;
+        let on_toggled = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input.map(|i| UserSettingDisabledRecipeMessage::Toggled(i.checked()))
+        });
+        let disabled = self
+            .user_settings
+            .as_ref()
+            .and_then(|us| us.read().ok().map(|us| us.is_recipe_disabled(&recipe.name)))
+            .unwrap_or(false);
+        html! {
+            <li>
+                <label>
+                    <input type="checkbox" checked={disabled} onchange={on_toggled}/>
+                    <RecipeIcon recipe={recipe}/>
+                    {recipe.name.clone()}
+                </label>
+            </li>
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use super::user_settings_page::UserSettingsPageMessage;
+use crate::{
+    components::{RecipeIcon, SpriteSheetIcon},
+    constants::GAME_DATA,
+    data::*,
+    WrappedUserSettings,
+};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlInputElement};
+use yew::prelude::*;
+
+#[derive(Debug)]
+pub struct UserSettingItemRecipe {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct UserSettingItemRecipeProperties {
+    pub item: String,
+    pub choices: Vec<&'static Recipe>,
+    pub callback: Callback<UserSettingsPageMessage>,
+}
+
+#[derive(Debug)]
+pub enum UserSettingItemRecipeMessage {
+    RecipeSelected(&'static Recipe),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
+}
+
+impl Component for UserSettingItemRecipe {
+    type Properties = UserSettingItemRecipeProperties;
+    type Message = UserSettingItemRecipeMessage;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(UserSettingItemRecipeMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
+
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            UserSettingItemRecipeMessage::RecipeSelected(recipe) => {
+                let props = ctx.props();
+                props.callback.emit(UserSettingsPageMessage::ChangeRecipe(
+                    props.item.clone(),
+                    recipe,
+                ));
+                false
+            }
+            UserSettingItemRecipeMessage::UserSettingsUpdated(user_settings) => {
+                self.user_settings = user_settings;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let on_selected = ctx.link().batch_callback(|e: Event| {
+            let target: Option<EventTarget> = e.target();
+            let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+            input
+                .and_then(|i| GAME_DATA.recipes.get(&i.value()))
+                .map(UserSettingItemRecipeMessage::RecipeSelected)
+        });
+        let selected_recipe = self.user_settings.as_ref().and_then(|us| {
+            us.read()
+                .ok()
+                .and_then(|us| us.recipe(&props.item).map(|rp| rp.get_name().to_owned()))
+        });
+        html! {
+            <li>
+            <SpriteSheetIcon name={props.item.clone()} prefix="item"/>
+            {
+                for props.choices.iter().map(|recipe| {
+                    html_nested! {
+                        <label title={recipe.name.clone()}>
+                            <input type="radio" name={format!("item-recipe-pref-{}", props.item)} checked={
+                                selected_recipe.as_deref() == Some(recipe.name.as_str())} onchange={on_selected.clone()} value={recipe.name.clone()}/>
+                            <RecipeIcon recipe={*recipe}/>
+                            {recipe.name.clone()}
+                        </label>
+                    }
+                })
+            }
+            </li>
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use super::user_settings_page::UserSettingsPageMessage;
-use crate::{components::SpriteSheetIcon, constants::GAME_DATA, data::*, USER_SETTINGS};
+use crate::{components::SpriteSheetIcon, constants::GAME_DATA, data::*, WrappedUserSettings};
+use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{EventTarget, HtmlInputElement};
 use yew::prelude::*;
 
 #[derive(Debug)]
-pub struct UserSettingRecipeCategory;
+pub struct UserSettingRecipeCategory {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct UserSettingRecipeCategoryProperties {
@@ -14,23 +19,45 @@ pub struct UserSettingRecipeCategoryProperties {
     pub callback: Callback<UserSettingsPageMessage>,
 }
 
+#[derive(Debug)]
+pub enum UserSettingRecipeCategoryMessage {
+    AssemblerSelected(&'static AssemblingMachine),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
+}
+
 impl Component for UserSettingRecipeCategory {
     type Properties = UserSettingRecipeCategoryProperties;
-    type Message = &'static AssemblingMachine;
+    type Message = UserSettingRecipeCategoryMessage;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(UserSettingRecipeCategoryMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let props = ctx.props();
-        props
-            .callback
-            .emit(UserSettingsPageMessage::ChangeAssembler(
-                props.category.clone(),
-                msg,
-            ));
-        false
+        match msg {
+            UserSettingRecipeCategoryMessage::AssemblerSelected(assembling_machine) => {
+                let props = ctx.props();
+                props
+                    .callback
+                    .emit(UserSettingsPageMessage::ChangeAssembler(
+                        props.category.clone(),
+                        assembling_machine,
+                    ));
+                false
+            }
+            UserSettingRecipeCategoryMessage::UserSettingsUpdated(user_settings) => {
+                self.user_settings = user_settings;
+                true
+            }
+        }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -39,7 +66,15 @@ impl Component for UserSettingRecipeCategory {
             log::info!("change");
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
-            input.and_then(|i| GAME_DATA.assembling_machines.get(&i.value()))
+            input
+                .and_then(|i| GAME_DATA.assembling_machines.get(&i.value()))
+                .map(UserSettingRecipeCategoryMessage::AssemblerSelected)
+        });
+        let selected_machine = self.user_settings.as_ref().and_then(|us| {
+            us.read().ok().and_then(|us| {
+                us.assembling_machine(&props.category)
+                    .map(|amp| amp.get_name().to_owned())
+            })
         });
         html! {
             <li>
@@ -49,13 +84,7 @@ impl Component for UserSettingRecipeCategory {
                     html_nested! {
                         <label>
                             <input type="radio" name={format!("recipe-category-pref-{}", props.category)} checked={
-                                USER_SETTINGS
-                                    .read().ok().and_then(|us| {
-                                        us.recipe_category_prefs
-                                            .get(&props.category)
-                                            .map(|amp| amp.name == am.name)
-                                    })
-                                .unwrap_or(false)} onchange={on_selected.clone()} value={am.name.clone()}/>
+                                selected_machine.as_deref() == Some(am.name.as_str())} onchange={on_selected.clone()} value={am.name.clone()}/>
                             <SpriteSheetIcon name={am.name.clone()} prefix="assembling-machine"/>
                         </label>
                     }
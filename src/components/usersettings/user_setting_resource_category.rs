@@ -1,11 +1,16 @@
 use super::user_settings_page::UserSettingsPageMessage;
-use crate::{constants::GAME_DATA, data::*, SpriteSheetIcon, USER_SETTINGS};
+use crate::{constants::GAME_DATA, data::*, SpriteSheetIcon, WrappedUserSettings};
+use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{EventTarget, HtmlInputElement};
 use yew::prelude::*;
 
 #[derive(Debug)]
-pub struct UserSettingResourceCategory;
+pub struct UserSettingResourceCategory {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
 
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct UserSettingResourceCategoryProperties {
@@ -14,23 +19,45 @@ pub struct UserSettingResourceCategoryProperties {
     pub callback: Callback<UserSettingsPageMessage>,
 }
 
+#[derive(Debug)]
+pub enum UserSettingResourceCategoryMessage {
+    MiningDrillSelected(&'static MiningDrill),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
+}
+
 impl Component for UserSettingResourceCategory {
     type Properties = UserSettingResourceCategoryProperties;
-    type Message = &'static MiningDrill;
+    type Message = UserSettingResourceCategoryMessage;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(UserSettingResourceCategoryMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        let props = ctx.props();
-        props
-            .callback
-            .emit(UserSettingsPageMessage::ChangeMiningDrill(
-                props.category.clone(),
-                msg,
-            ));
-        false
+        match msg {
+            UserSettingResourceCategoryMessage::MiningDrillSelected(mining_drill) => {
+                let props = ctx.props();
+                props
+                    .callback
+                    .emit(UserSettingsPageMessage::ChangeMiningDrill(
+                        props.category.clone(),
+                        mining_drill,
+                    ));
+                false
+            }
+            UserSettingResourceCategoryMessage::UserSettingsUpdated(user_settings) => {
+                self.user_settings = user_settings;
+                true
+            }
+        }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -38,7 +65,15 @@ impl Component for UserSettingResourceCategory {
         let on_selected = ctx.link().batch_callback(|e: Event| {
             let target: Option<EventTarget> = e.target();
             let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
-            input.and_then(|i| GAME_DATA.mining_drills.get(&i.value()))
+            input
+                .and_then(|i| GAME_DATA.mining_drills.get(&i.value()))
+                .map(UserSettingResourceCategoryMessage::MiningDrillSelected)
+        });
+        let selected_drill = self.user_settings.as_ref().and_then(|us| {
+            us.read().ok().and_then(|us| {
+                us.mining_drill(&props.category)
+                    .map(|mdp| mdp.get_name().to_owned())
+            })
         });
         html! {
             <li>
@@ -48,13 +83,7 @@ impl Component for UserSettingResourceCategory {
                     html_nested! {
                         <label>
                             <input type="radio" name={format!("resource-category-pref-{}", props.category)} checked={
-                                USER_SETTINGS
-                                    .read().ok().and_then(|us| {
-                                        us.resource_category_prefs
-                                            .get(&props.category)
-                                            .map(|mdp| mdp.name == md.name)
-                                    })
-                                .unwrap_or(false)} onchange={on_selected.clone()} value={md.name.clone()}/>
+                                selected_drill.as_deref() == Some(md.name.as_str())} onchange={on_selected.clone()} value={md.name.clone()}/>
                             <SpriteSheetIcon name={md.name.clone()} prefix="mining-drill"/>
                         </label>
                     }
@@ -1,47 +1,315 @@
-use crate::{constants::GAME_DATA, data::*, Route, USER_SETTINGS};
+use crate::{
+    components::{
+        BeaconSetting, BeaconSettingInput, ImportedItems, ItemIcon, ModuleSlots, RecipeIcon,
+        SpriteSheetIcon,
+    },
+    constants::{DEFAULT_ITEM, GAME_DATA},
+    data::*,
+    prototype_ref::{AssemblingMachineRef, MiningDrillRef, ModuleRef, RecipeRef},
+    Route, WrappedUserSettings,
+};
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 use super::{
+    user_setting_disabled_recipe::UserSettingDisabledRecipe,
+    user_setting_item_recipe::UserSettingItemRecipe,
     user_setting_recipe_category::UserSettingRecipeCategory,
     user_setting_resource_category::UserSettingResourceCategory,
 };
 
 #[derive(Debug)]
-pub struct UserSettingsPage;
+pub struct UserSettingsPage {
+    user_settings: Option<Rc<WrappedUserSettings>>,
+    #[allow(dead_code)]
+    user_settings_context_listener: ContextHandle<Option<Rc<WrappedUserSettings>>>,
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum UserSettingsPageMessage {
     ChangeAssembler(String, &'static AssemblingMachine),
     ChangeMiningDrill(String, &'static MiningDrill),
+    ChangeRecipe(String, &'static Recipe),
+    ChangeRecipeDisabled(&'static Recipe, bool),
+    AddImportedItem,
+    RemoveImportedItem(usize),
+    ChangeImportedItem(usize, String),
+    ChangeRecipeCategoryModules(String, Vec<String>),
+    ChangeResourceCategoryModules(String, Vec<String>),
+    ChangeRecipeCategoryBeacons(String, BeaconSetting),
+    ChangeDifficulty(Difficulty),
+    ChangeFuel(String),
+    ChangeBelt(String),
+    ChangeInserter(String),
+    ChangeInserterStackBonus(u32),
+    ChangeStackInserterBonus(u32),
+    ChangeWaterSource(String, String),
+    ChangeGenerator(String),
+    ChangeMiningProductivityLevel(u32),
+    ChangeResourceYield(String, f64),
+    UserSettingsUpdated(Option<Rc<WrappedUserSettings>>),
 }
 
 impl Component for UserSettingsPage {
     type Message = UserSettingsPageMessage;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let scope = ctx.link();
+        let (user_settings, user_settings_context_listener) = scope
+            .context(scope.callback(UserSettingsPageMessage::UserSettingsUpdated))
+            .expect("User Settings context was not provided");
+
+        Self {
+            user_settings,
+            user_settings_context_listener,
+        }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         log::info!("update callback");
-        let mut user_settings = USER_SETTINGS.write().unwrap();
+        if let UserSettingsPageMessage::UserSettingsUpdated(user_settings) = msg {
+            self.user_settings = user_settings;
+            return true;
+        }
+        let mut user_settings = match self.user_settings.as_ref().and_then(|us| us.write().ok()) {
+            Some(user_settings) => user_settings,
+            None => {
+                log::warn!("User settings are not ready yet, the change is dropped");
+                return false;
+            }
+        };
         match msg {
             UserSettingsPageMessage::ChangeAssembler(recipe_category, assembling_machine) => {
-                user_settings.change_recipe_category(&recipe_category, assembling_machine)
+                user_settings.change_recipe_category(
+                    &recipe_category,
+                    AssemblingMachineRef::new(assembling_machine.name.clone()),
+                )
             }
             UserSettingsPageMessage::ChangeMiningDrill(resource_category, mining_drill) => {
-                user_settings.change_resource_category(&resource_category, mining_drill)
+                user_settings.change_resource_category(
+                    &resource_category,
+                    MiningDrillRef::new(mining_drill.name.clone()),
+                )
+            }
+            UserSettingsPageMessage::ChangeRecipe(item, recipe) => {
+                user_settings.change_item_recipe(&item, RecipeRef::new(recipe.name.clone()))
+            }
+            UserSettingsPageMessage::ChangeRecipeDisabled(recipe, disabled) => {
+                user_settings.change_recipe_disabled(RecipeRef::new(recipe.name.clone()), disabled)
+            }
+            UserSettingsPageMessage::AddImportedItem => {
+                let mut items = user_settings.imported_items().to_vec();
+                items.push(DEFAULT_ITEM.into());
+                user_settings.change_imported_items(items);
+                return true;
+            }
+            UserSettingsPageMessage::RemoveImportedItem(idx) => {
+                let mut items = user_settings.imported_items().to_vec();
+                items.remove(idx);
+                user_settings.change_imported_items(items);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeImportedItem(idx, item) => {
+                let mut items = user_settings.imported_items().to_vec();
+                items[idx] = item;
+                user_settings.change_imported_items(items);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeRecipeCategoryModules(category, modules) => {
+                user_settings.change_recipe_category_modules(
+                    &category,
+                    modules.into_iter().map(ModuleRef::new).collect(),
+                );
+                return true;
+            }
+            UserSettingsPageMessage::ChangeResourceCategoryModules(category, modules) => {
+                user_settings.change_resource_category_modules(
+                    &category,
+                    modules.into_iter().map(ModuleRef::new).collect(),
+                );
+                return true;
+            }
+            UserSettingsPageMessage::ChangeRecipeCategoryBeacons(category, beacons) => {
+                user_settings.change_recipe_category_beacons(&category, beacons);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeDifficulty(difficulty) => {
+                user_settings.change_difficulty(difficulty);
+                return true;
             }
+            UserSettingsPageMessage::ChangeFuel(fuel) => {
+                user_settings.change_fuel(fuel);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeBelt(belt) => {
+                user_settings.change_belt(belt);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeInserter(inserter) => {
+                user_settings.change_inserter(inserter);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeInserterStackBonus(stack_bonus) => {
+                user_settings.change_inserter_stack_bonus(stack_bonus);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeStackInserterBonus(stack_bonus) => {
+                user_settings.change_stack_inserter_bonus(stack_bonus);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeWaterSource(fluid, source) => {
+                user_settings.change_water_source(fluid, source);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeGenerator(generator) => {
+                user_settings.change_generator(generator);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeMiningProductivityLevel(level) => {
+                user_settings.change_mining_productivity_level(level);
+                return true;
+            }
+            UserSettingsPageMessage::ChangeResourceYield(resource, percent) => {
+                user_settings.change_resource_yield(resource, percent);
+                return true;
+            }
+            // Handled before taking the lock
+            UserSettingsPageMessage::UserSettingsUpdated(_) => {}
         };
         false
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let user_settings = match self.user_settings.as_ref().and_then(|us| us.read().ok()) {
+            Some(user_settings) => user_settings,
+            None => return html! { <p>{ "User settings are not ready yet" }</p> },
+        };
+        let module_names = |modules: &[ModuleRef]| -> Vec<String> {
+            modules.iter().map(|m| m.get_name().to_string()).collect()
+        };
+        let modules = GAME_DATA.modules_sorted();
+        let difficulty = user_settings.difficulty();
+        let fuels = GAME_DATA.fuels(&GAME_DATA.burner_fuel_categories());
+        let belts = GAME_DATA.belts_sorted();
+        let inserters = GAME_DATA.inserters_sorted();
+        let infinite_resources = GAME_DATA.infinite_resources();
+        let water_sources = GAME_DATA.water_sources(difficulty);
+        let generators = GAME_DATA.generators_sorted();
+        let on_mining_productivity_change = link.batch_callback(|e: Event| {
+            e.target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|i| i.value().parse().ok())
+                .map(UserSettingsPageMessage::ChangeMiningProductivityLevel)
+        });
+        let on_stack_bonus_change = link.batch_callback(|e: Event| {
+            e.target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|i| i.value().parse().ok())
+                .map(UserSettingsPageMessage::ChangeInserterStackBonus)
+        });
+        let on_stack_inserter_bonus_change = link.batch_callback(|e: Event| {
+            e.target()
+                .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                .and_then(|i| i.value().parse().ok())
+                .map(UserSettingsPageMessage::ChangeStackInserterBonus)
+        });
         html! {
             <div id="usersettings">
                 <p><Link<Route> to={Route::Home}>{"Go back"}</Link<Route>></p>
+                <div id="usersettings_difficulty">
+                    <p>{"Recipe difficulty:"}</p>
+                    <label>
+                        <input type="radio" name="difficulty" checked={difficulty == Difficulty::Normal}
+                            onchange={link.callback(|_| UserSettingsPageMessage::ChangeDifficulty(Difficulty::Normal))} />
+                        {"normal"}
+                    </label>
+                    <label>
+                        <input type="radio" name="difficulty" checked={difficulty == Difficulty::Expensive}
+                            onchange={link.callback(|_| UserSettingsPageMessage::ChangeDifficulty(Difficulty::Expensive))} />
+                        {"expensive"}
+                    </label>
+                </div>
+                if !fuels.is_empty() {
+                    <div id="usersettings_fuel">
+                        <p>{"Fuel for burner machines:"}</p>
+                        {
+                            for fuels.into_iter().map(|fuel| {
+                                let fuel_name = fuel.name.clone();
+                                html_nested! {
+                                    <label>
+                                        <input type="radio" name="fuel" checked={user_settings.fuel() == fuel.name}
+                                            onchange={link.callback(move |_| UserSettingsPageMessage::ChangeFuel(fuel_name.clone()))} />
+                                        <ItemIcon item={fuel.name.clone()} />
+                                    </label>
+                                }
+                            })
+                        }
+                    </div>
+                }
+                if !belts.is_empty() {
+                    <div id="usersettings_belt">
+                        <p>{"Belt for counting belts of item flows:"}</p>
+                        {
+                            for belts.into_iter().map(|belt| {
+                                let belt_name = belt.name.clone();
+                                html_nested! {
+                                    <label>
+                                        <input type="radio" name="belt" checked={user_settings.belt() == belt.name}
+                                            onchange={link.callback(move |_| UserSettingsPageMessage::ChangeBelt(belt_name.clone()))} />
+                                        <SpriteSheetIcon prefix="transport-belt" name={belt.name.clone()} />
+                                    </label>
+                                }
+                            })
+                        }
+                    </div>
+                }
+                if !inserters.is_empty() {
+                    <div id="usersettings_inserter">
+                        <p>{"Inserter for counting inserters of machines:"}</p>
+                        {
+                            for inserters.into_iter().map(|inserter| {
+                                let inserter_name = inserter.name.clone();
+                                html_nested! {
+                                    <label>
+                                        <input type="radio" name="inserter" checked={user_settings.inserter() == inserter.name}
+                                            onchange={link.callback(move |_| UserSettingsPageMessage::ChangeInserter(inserter_name.clone()))} />
+                                        <SpriteSheetIcon prefix="inserter" name={inserter.name.clone()} />
+                                    </label>
+                                }
+                            })
+                        }
+                        <label>
+                            {"Stack bonus: "}
+                            <input type="text" onchange={on_stack_bonus_change} value={user_settings.inserter_stack_bonus().to_string()} />
+                        </label>
+                        <label>
+                            {"Stack inserter bonus: "}
+                            <input type="text" onchange={on_stack_inserter_bonus_change} value={user_settings.stack_inserter_bonus().to_string()} />
+                        </label>
+                    </div>
+                }
+                if !generators.is_empty() {
+                    <div id="usersettings_generator">
+                        <p>{"Generator for electricity:"}</p>
+                        {
+                            for generators.into_iter().map(|generator| {
+                                let generator_name = generator.name.clone();
+                                html_nested! {
+                                    <label>
+                                        <input type="radio" name="generator" checked={user_settings.generator() == generator.name}
+                                            onchange={link.callback(move |_| UserSettingsPageMessage::ChangeGenerator(generator_name.clone()))} />
+                                        <SpriteSheetIcon prefix="generator" name={generator.name.clone()} />
+                                    </label>
+                                }
+                            })
+                        }
+                    </div>
+                }
                 <div id="usersettings_assemblingmachine">
                     <p>{"Assembling machines and furnaces:"}</p>
                     <ul>
@@ -66,6 +334,179 @@ impl Component for UserSettingsPage {
                     }
                     </ul>
                 </div>
+                if !water_sources.is_empty() {
+                    <div id="usersettings_watersource">
+                        <p>{"Water sources:"}</p>
+                        <ul>
+                        {
+                            for water_sources.into_iter().map(|sources| {
+                                // The first offshore pump is used when there's no preference
+                                let selected = user_settings
+                                    .water_source(&sources.fluid)
+                                    .map(str::to_string)
+                                    .or_else(|| sources.offshore_pumps.first().map(|op| op.name.clone()));
+                                let group = format!("water_source_{}", sources.fluid);
+                                let source_input = |name: &str| {
+                                    let fluid = sources.fluid.clone();
+                                    let source = name.to_string();
+                                    html! {
+                                        <input type="radio" name={group.clone()} checked={selected.as_deref() == Some(name)}
+                                            onchange={link.callback(move |_| UserSettingsPageMessage::ChangeWaterSource(fluid.clone(), source.clone()))} />
+                                    }
+                                };
+                                html_nested! {
+                                    <li>
+                                        <ItemIcon item={sources.fluid.clone()} />
+                                        {
+                                            for sources.offshore_pumps.iter().map(|offshore_pump| html! {
+                                                <label>
+                                                    { source_input(&offshore_pump.name) }
+                                                    <SpriteSheetIcon prefix="offshore-pump" name={offshore_pump.name.clone()} />
+                                                </label>
+                                            })
+                                        }
+                                        {
+                                            for sources.recipes.iter().map(|recipe| html! {
+                                                <label>
+                                                    { source_input(&recipe.name) }
+                                                    <RecipeIcon recipe={*recipe} />
+                                                </label>
+                                            })
+                                        }
+                                    </li>
+                                }
+                            })
+                        }
+                        </ul>
+                    </div>
+                }
+                <div id="usersettings_mining">
+                    <label>
+                        {"Mining productivity research level: "}
+                        <input type="text" onchange={on_mining_productivity_change} value={user_settings.mining_productivity_level().to_string()} />
+                    </label>
+                    if !infinite_resources.is_empty() {
+                        <p>{"Yield of infinite resources, %:"}</p>
+                        <ul>
+                        {
+                            for infinite_resources.into_iter().map(|resource| {
+                                let resource_name = resource.name.clone();
+                                let on_yield_change = link.batch_callback(move |e: Event| {
+                                    e.target()
+                                        .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+                                        .and_then(|i| i.value().parse().ok())
+                                        .map(|percent| UserSettingsPageMessage::ChangeResourceYield(resource_name.clone(), percent))
+                                });
+                                html_nested! {
+                                    <li>
+                                        {format!("{}: ", resource.name)}
+                                        <input type="text" onchange={on_yield_change} value={user_settings.resource_yield(&resource.name).to_string()} />
+                                    </li>
+                                }
+                            })
+                        }
+                        </ul>
+                    }
+                </div>
+                <div id="usersettings_recipe">
+                    <p>{"Recipes:"}</p>
+                    <ul>
+                    {
+                        for GAME_DATA.items_with_multiple_recipes(difficulty).iter().map(|v| {
+                            html_nested! {
+                                <UserSettingItemRecipe item={v.0.clone()} callback={ctx.link().callback(|m| m)} choices={v.1.clone()} />
+                            }
+                        })
+                    }
+                    </ul>
+                </div>
+                <div id="usersettings_disabledrecipes">
+                    <p>{"Disabled recipes:"}</p>
+                    <ul>
+                    {
+                        for GAME_DATA.recipes_with_alternatives(difficulty).into_iter().map(|recipe| {
+                            html_nested! {
+                                <UserSettingDisabledRecipe recipe={recipe} callback={ctx.link().callback(|m| m)} />
+                            }
+                        })
+                    }
+                    </ul>
+                </div>
+                <div id="usersettings_recipecategorymodules">
+                    <p>{"Default modules for assembling machines and furnaces:"}</p>
+                    <ul>
+                    {
+                        for GAME_DATA.recipe_categories_with_module_slots().into_iter().map(|(category, slots)| {
+                            let selected = module_names(user_settings.recipe_category_modules(&category));
+                            let callback_category = category.clone();
+                            html_nested! {
+                                <li>
+                                    <p>{ category.clone() }</p>
+                                    <ModuleSlots
+                                        slots={slots}
+                                        selected={selected}
+                                        choices={modules.clone()}
+                                        group={format!("recipe-category-{}", category)}
+                                        callback={link.callback(move |m| UserSettingsPageMessage::ChangeRecipeCategoryModules(callback_category.clone(), m))} />
+                                </li>
+                            }
+                        })
+                    }
+                    </ul>
+                </div>
+                if !GAME_DATA.beacons.is_empty() {
+                    <div id="usersettings_recipecategorybeacons">
+                        <p>{"Default beacons for assembling machines and furnaces:"}</p>
+                        <ul>
+                        {
+                            for GAME_DATA.recipe_categories_with_module_slots().into_keys().map(|category| {
+                                let setting = user_settings.recipe_category_beacons(&category).cloned().unwrap_or_default();
+                                let callback_category = category.clone();
+                                html_nested! {
+                                    <li>
+                                        <p>{ category.clone() }</p>
+                                        <BeaconSettingInput
+                                            setting={setting}
+                                            group={format!("recipe-category-{}", category)}
+                                            callback={link.callback(move |b| UserSettingsPageMessage::ChangeRecipeCategoryBeacons(callback_category.clone(), b))} />
+                                    </li>
+                                }
+                            })
+                        }
+                        </ul>
+                    </div>
+                }
+                <div id="usersettings_resourcecategorymodules">
+                    <p>{"Default modules for mining drills:"}</p>
+                    <ul>
+                    {
+                        for GAME_DATA.resource_categories_with_module_slots().into_iter().map(|(category, slots)| {
+                            let selected = module_names(user_settings.resource_category_modules(&category));
+                            let callback_category = category.clone();
+                            html_nested! {
+                                <li>
+                                    <p>{ category.clone() }</p>
+                                    <ModuleSlots
+                                        slots={slots}
+                                        selected={selected}
+                                        choices={modules.clone()}
+                                        group={format!("resource-category-{}", category)}
+                                        callback={link.callback(move |m| UserSettingsPageMessage::ChangeResourceCategoryModules(callback_category.clone(), m))} />
+                                </li>
+                            }
+                        })
+                    }
+                    </ul>
+                </div>
+                <div id="usersettings_importeditems">
+                    <p>{"Items imported by default:"}</p>
+                    <ImportedItems
+                        items={user_settings.imported_items().to_vec()}
+                        group="settings-import"
+                        on_add={link.callback(|_| UserSettingsPageMessage::AddImportedItem)}
+                        on_remove={link.callback(UserSettingsPageMessage::RemoveImportedItem)}
+                        on_change={link.callback(|(i, item)| UserSettingsPageMessage::ChangeImportedItem(i, item))} />
+                </div>
             </div>
         }
     }
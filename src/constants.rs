@@ -9,13 +9,20 @@ pub const ORIGINAL_ICON_SIZE: usize = 64;
 pub const DOWNSCALE: usize = 2;
 pub const SPRITESHEET_SIZE: usize = ORIGINAL_SPRITESHEET_SIZE / DOWNSCALE;
 pub const ICON_SIZE: usize = ORIGINAL_ICON_SIZE / DOWNSCALE;
-pub const RECURSION_LIMIT: usize = 5000;
-pub const VERY_SMALL: f64 = 1e-10;
-pub const RECIPE_BLACKLIST: &[&str] = &[
-    "coal-liquefaction",
-    "kovarex-enrichment-process",
-    "nuclear-fuel-reprocessing",
-]; // allow_decomposition = false
+// Fuel burnt by burner machines in fresh user settings
+pub const DEFAULT_FUEL: &str = "coal";
+
+// Belt tier used to count belts in fresh user settings
+pub const DEFAULT_BELT: &str = "transport-belt";
+
+pub const DEFAULT_INSERTER: &str = "inserter";
+
+// Generator making electricity in fresh user settings
+pub const DEFAULT_GENERATOR: &str = "steam-engine";
+
+pub const DEFAULT_TRIP_TIME: f64 = 120.0;
+
+pub const DEFAULT_TRAIN_WAGONS: u32 = 4;
 
 pub static ICON_MAP: Lazy<HashMap<String, (usize, usize)>> = Lazy::new(|| {
     let json_mapping = include_bytes!("../assets/generated/spritesheet-mapping.json");
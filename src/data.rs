@@ -1,6 +1,6 @@
 use image::Rgba;
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct GameData {
@@ -12,8 +12,47 @@ pub struct GameData {
     pub mining_drills: HashMap<String, MiningDrill>,
     pub offshore_pumps: HashMap<String, OffshorePump>,
     pub resources: HashMap<String, Resource>,
+    #[serde(default)]
+    pub modules: HashMap<String, Module>,
+    #[serde(default)]
+    pub beacons: HashMap<String, Beacon>,
+    #[serde(default)]
+    pub boilers: HashMap<String, Boiler>,
+    #[serde(default)]
+    pub generators: HashMap<String, Generator>,
+    #[serde(default)]
+    pub reactors: HashMap<String, Reactor>,
+    #[serde(default)]
+    pub rocket_silos: HashMap<String, RocketSilo>,
+    #[serde(default)]
+    pub labs: HashMap<String, Lab>,
+    #[serde(default)]
+    pub technologies: HashMap<String, Technology>,
+    #[serde(default)]
+    pub transport_belts: HashMap<String, TransportBelt>,
+    #[serde(default)]
+    pub pumps: HashMap<String, Pump>,
+    #[serde(default)]
+    pub inserters: HashMap<String, Inserter>,
+    #[serde(default)]
+    pub logistic_robots: HashMap<String, LogisticRobot>,
+    #[serde(default)]
+    pub cargo_wagons: HashMap<String, CargoWagon>,
+    #[serde(default)]
+    pub fluid_wagons: HashMap<String, FluidWagon>,
 }
 
+/// Pseudo item produced by generators, one unit is one megajoule of electricity
+pub const ELECTRIC_ENERGY: &str = "electric-energy";
+/// Pseudo item produced by reactors and consumed by heat exchangers, one unit is one megajoule
+pub const HEAT_ENERGY: &str = "heat";
+/// Joules in one unit of the energy pseudo items
+pub const ENERGY_UNIT: f64 = 1e6;
+
+// Fluids are heated from 15 degrees and every degree of one unit takes 200 J, like for water and steam
+const FLUID_BASE_TEMPERATURE: f64 = 15.0;
+const FLUID_HEAT_CAPACITY: f64 = 200.0;
+
 type VecMap<T> = Vec<(String, T)>;
 
 impl GameData {
@@ -74,6 +113,242 @@ impl GameData {
         result
     }
 
+    pub fn recipes_for_item(&self, item: &str, difficulty: Difficulty) -> Vec<&Recipe> {
+        let mut result: Vec<&Recipe> = self
+            .recipes
+            .values()
+            .filter(|recipe| {
+                recipe.allow_decomposition(difficulty) && recipe.net_output(item, difficulty) > 0.0
+            })
+            .collect();
+        result.sort_unstable_by(|rec1, rec2| {
+            (rec1.name != item)
+                .cmp(&(rec2.name != item))
+                .then_with(|| rec1.name.cmp(&rec2.name))
+        });
+        result
+    }
+
+    pub fn items_with_multiple_recipes(
+        &self,
+        difficulty: Difficulty,
+    ) -> HashMap<String, Vec<&Recipe>> {
+        let mut result = HashMap::new();
+        for item in self.items.keys() {
+            let recipes = self.recipes_for_item(item, difficulty);
+            if recipes.len() > 1 {
+                result.insert(item.clone(), recipes);
+            }
+        }
+        result
+    }
+
+    pub fn recipes_with_alternatives(&self, difficulty: Difficulty) -> Vec<&Recipe> {
+        let mut result: Vec<&Recipe> = self
+            .items_with_multiple_recipes(difficulty)
+            .into_values()
+            .flatten()
+            .collect();
+        result.sort_unstable_by(|rec1, rec2| rec1.name.cmp(&rec2.name));
+        result.dedup_by(|rec1, rec2| rec1.name == rec2.name);
+        result
+    }
+
+    /// Fuel categories accepted by machines with burner energy sources
+    pub fn burner_fuel_categories(&self) -> Vec<String> {
+        let mut result: Vec<String> = self
+            .assembling_machines
+            .values()
+            .filter_map(|am| am.energy_source.as_ref())
+            .chain(
+                self.mining_drills
+                    .values()
+                    .filter_map(|md| md.energy_source.as_ref()),
+            )
+            .filter(|source| source.source_type == EnergySourceType::Burner)
+            .flat_map(|source| source.fuel_categories())
+            .collect();
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// Items that can be burnt in any of the fuel categories, sorted by name
+    pub fn fuels(&self, fuel_categories: &[String]) -> Vec<&Item> {
+        let mut result: Vec<&Item> = self
+            .items
+            .values()
+            .filter(|item| {
+                item.fuel_value > 0.0
+                    && item
+                        .fuel_category
+                        .as_ref()
+                        .is_some_and(|category| fuel_categories.contains(category))
+            })
+            .collect();
+        result.sort_unstable_by(|item1, item2| item1.name.cmp(&item2.name));
+        result
+    }
+
+    pub fn technologies_sorted(&self) -> Vec<&Technology> {
+        let mut result: Vec<&Technology> = self.technologies.values().collect();
+        result.sort_unstable_by(|t1, t2| t1.name.cmp(&t2.name));
+        result
+    }
+
+    /// Items accepted by any lab, in the order of the first lab that accepts them
+    pub fn science_packs(&self) -> Vec<String> {
+        let mut labs: Vec<&Lab> = self.labs.values().collect();
+        labs.sort_unstable_by(|l1, l2| l1.name.cmp(&l2.name));
+        let mut result: Vec<String> = Vec::new();
+        for input in labs.into_iter().flat_map(|lab| &lab.inputs) {
+            if !result.contains(input) {
+                result.push(input.clone());
+            }
+        }
+        result
+    }
+
+    /// First lab by name that accepts all of the science packs
+    pub fn lab_for(&self, science_packs: &[String]) -> Option<&Lab> {
+        self.labs
+            .values()
+            .filter(|lab| science_packs.iter().all(|pack| lab.inputs.contains(pack)))
+            .min_by(|l1, l2| l1.name.cmp(&l2.name))
+    }
+
+    /// Belts from the slowest to the fastest
+    pub fn belts_sorted(&self) -> Vec<&TransportBelt> {
+        let mut result: Vec<&TransportBelt> = self.transport_belts.values().collect();
+        result.sort_unstable_by(|b1, b2| {
+            b1.speed
+                .total_cmp(&b2.speed)
+                .then_with(|| b1.name.cmp(&b2.name))
+        });
+        result
+    }
+
+    /// Offshore pumps pumping a fluid, sorted by name
+    pub fn offshore_pumps_for_fluid(&self, fluid: &str) -> Vec<&OffshorePump> {
+        let mut result: Vec<&OffshorePump> = self
+            .offshore_pumps
+            .values()
+            .filter(|offshore_pump| offshore_pump.fluid == fluid)
+            .collect();
+        result.sort_unstable_by(|op1, op2| op1.name.cmp(&op2.name));
+        result
+    }
+
+    /// Fluids pumped by offshore pumps that have more than one source to choose from
+    pub fn water_sources(&self, difficulty: Difficulty) -> Vec<WaterSources<'_>> {
+        let fluids: BTreeSet<&str> = self
+            .offshore_pumps
+            .values()
+            .map(|offshore_pump| offshore_pump.fluid.as_str())
+            .collect();
+        fluids
+            .into_iter()
+            .map(|fluid| WaterSources {
+                fluid: fluid.to_string(),
+                offshore_pumps: self.offshore_pumps_for_fluid(fluid),
+                recipes: self.recipes_for_item(fluid, difficulty),
+            })
+            .filter(|sources| sources.offshore_pumps.len() + sources.recipes.len() > 1)
+            .collect()
+    }
+
+    /// Resources like crude oil whose yield depends on the resource patch
+    pub fn infinite_resources(&self) -> Vec<&Resource> {
+        let mut result: Vec<&Resource> = self
+            .resources
+            .values()
+            .filter(|resource| resource.infinite)
+            .collect();
+        result.sort_unstable_by(|r1, r2| r1.name.cmp(&r2.name));
+        result
+    }
+
+    /// Generators from the lowest to the highest steam temperature they use
+    pub fn generators_sorted(&self) -> Vec<&Generator> {
+        let mut result: Vec<&Generator> = self.generators.values().collect();
+        result.sort_unstable_by(|g1, g2| {
+            g1.maximum_temperature
+                .total_cmp(&g2.maximum_temperature)
+                .then_with(|| g1.name.cmp(&g2.name))
+        });
+        result
+    }
+
+    /// Inserters from the slowest to the fastest
+    pub fn inserters_sorted(&self) -> Vec<&Inserter> {
+        let mut result: Vec<&Inserter> = self.inserters.values().collect();
+        result.sort_unstable_by(|i1, i2| {
+            i1.rotation_speed
+                .total_cmp(&i2.rotation_speed)
+                .then_with(|| i1.name.cmp(&i2.name))
+        });
+        result
+    }
+
+    /// Everything that is moved through pipes instead of belts
+    pub fn fluids(&self) -> HashSet<&str> {
+        let mut result = HashSet::new();
+        for recipe in self.recipes.values() {
+            for difficulty in [Difficulty::Normal, Difficulty::Expensive] {
+                result.extend(recipe.fluids(difficulty));
+            }
+        }
+        for resource in self.resources.values() {
+            result.extend(resource.fluids());
+        }
+        result.extend(self.offshore_pumps.values().map(|op| op.fluid.as_str()));
+        result
+    }
+
+    /// Items moved by belts, inserters and robots, fluids and energy are not
+    pub fn is_solid_item(&self, item: &str) -> bool {
+        item != ELECTRIC_ENERGY && item != HEAT_ENERGY && !self.fluids().contains(item)
+    }
+
+    pub fn modules_sorted(&self) -> Vec<&Module> {
+        let mut result: Vec<&Module> = self.modules.values().collect();
+        result.sort_unstable_by(|m1, m2| {
+            m1.category
+                .cmp(&m2.category)
+                .then_with(|| m1.tier.cmp(&m2.tier))
+                .then_with(|| m1.name.cmp(&m2.name))
+        });
+        result
+    }
+
+    pub fn recipe_categories_with_module_slots(&self) -> HashMap<String, usize> {
+        let mut result: HashMap<String, usize> = HashMap::new();
+        for assembling_machine in self.assembling_machines.values() {
+            let slots = assembling_machine.module_slots();
+            if slots > 0 {
+                for category in &assembling_machine.crafting_categories {
+                    let entry = result.entry(category.clone()).or_insert(0);
+                    *entry = (*entry).max(slots);
+                }
+            }
+        }
+        result
+    }
+
+    pub fn resource_categories_with_module_slots(&self) -> HashMap<String, usize> {
+        let mut result: HashMap<String, usize> = HashMap::new();
+        for mining_drill in self.mining_drills.values() {
+            let slots = mining_drill.module_slots();
+            if slots > 0 {
+                for category in &mining_drill.resource_categories {
+                    let entry = result.entry(category.clone()).or_insert(0);
+                    *entry = (*entry).max(slots);
+                }
+            }
+        }
+        result
+    }
+
     pub fn resource_categories_with_multiple_mining_drills(
         &self,
     ) -> HashMap<String, Vec<&MiningDrill>> {
@@ -112,6 +387,26 @@ pub struct Item {
     pub name: String,
     #[serde(default = "default_subgroup")]
     pub subgroup: String,
+    #[serde(default, deserialize_with = "deserialize_energy")]
+    pub fuel_value: f64,
+    pub fuel_category: Option<String>,
+    /// Fluids and energy don't stack
+    #[serde(default)]
+    pub stack_size: u32,
+    pub rocket_launch_product: Option<RecipeResult>,
+    #[serde(default)]
+    pub rocket_launch_products: Vec<RecipeResult>,
+}
+
+impl Item {
+    /// Items received when this item is launched in a rocket
+    pub fn launch_products(&self) -> Vec<(String, f64)> {
+        self.rocket_launch_product
+            .iter()
+            .chain(&self.rocket_launch_products)
+            .map(Into::into)
+            .collect()
+    }
 }
 
 fn default_subgroup() -> String {
@@ -168,6 +463,8 @@ fn default_alpha() -> f64 {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Recipe {
+    #[serde(alias = "icons")]
+    pub icon: Option<Icon>,
     pub name: String,
     #[serde(default = "default_recipe_category")]
     pub category: String,
@@ -176,35 +473,81 @@ pub struct Recipe {
 }
 
 impl Recipe {
-    fn get_recipe_data(&self) -> &RecipeData {
+    fn get_recipe_data(&self, difficulty: Difficulty) -> &RecipeData {
         match &self.recipe_data {
             RecipeBody::Simple { data } => data,
-            RecipeBody::NormalAndExpensive {
-                normal,
-                expensive: _,
-            } => normal,
+            RecipeBody::NormalAndExpensive { normal, expensive } => match difficulty {
+                Difficulty::Normal => normal,
+                Difficulty::Expensive => expensive,
+            },
         }
     }
 
-    pub fn produces(&self) -> Vec<(String, f64)> {
-        (&self.get_recipe_data().results).into()
+    pub fn produces(&self, difficulty: Difficulty) -> Vec<(String, f64)> {
+        (&self.get_recipe_data(difficulty).results).into()
+    }
+
+    /// Ingredients and results that are fluids
+    pub fn fluids(&self, difficulty: Difficulty) -> Vec<&str> {
+        let data = self.get_recipe_data(difficulty);
+        data.ingredients
+            .iter()
+            .filter(|ingredient| ingredient.item_type() == RecipeItemType::Fluid)
+            .map(|ingredient| ingredient.name())
+            .chain(data.results.fluids())
+            .collect()
     }
 
-    pub fn consumes(&self) -> Vec<(String, f64)> {
-        self.get_recipe_data()
+    /// Ingredients with their amount and whether they are items or fluids
+    pub fn ingredients(&self, difficulty: Difficulty) -> Vec<(String, f64, RecipeItemType)> {
+        self.get_recipe_data(difficulty)
             .ingredients
             .iter()
             .map(Into::into)
             .collect()
     }
 
-    pub fn energy_required(&self) -> f64 {
-        self.get_recipe_data().energy_required
+    /// Amount of the item made in one craft minus the amount used up by it,
+    /// recipes looping back into their ingredients only produce if this is positive
+    pub fn net_output(&self, item: &str, difficulty: Difficulty) -> f64 {
+        let produced: f64 = self
+            .produces(difficulty)
+            .iter()
+            .filter(|(name, _)| name == item)
+            .map(|(_, amount)| amount)
+            .sum();
+        let consumed: f64 = self
+            .consumes(difficulty)
+            .iter()
+            .filter(|(name, _)| name == item)
+            .map(|(_, amount)| amount)
+            .sum();
+        produced - consumed
     }
 
-    pub fn allow_decomposition(&self) -> bool {
-        self.get_recipe_data().allow_decomposition
+    pub fn consumes(&self, difficulty: Difficulty) -> Vec<(String, f64)> {
+        self.ingredients(difficulty)
+            .into_iter()
+            .map(|(name, amount, _)| (name, amount))
+            .collect()
     }
+
+    pub fn energy_required(&self, difficulty: Difficulty) -> f64 {
+        self.get_recipe_data(difficulty).energy_required
+    }
+
+    pub fn allow_decomposition(&self, difficulty: Difficulty) -> bool {
+        self.get_recipe_data(difficulty).allow_decomposition
+    }
+}
+
+/// Recipe set used by the game, recipes without an expensive variant are the same in both
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty {
+    #[default]
+    Normal,
+    Expensive,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -266,6 +609,19 @@ impl From<&RecipeResults> for Vec<(String, f64)> {
     }
 }
 
+impl RecipeResults {
+    fn fluids(&self) -> impl Iterator<Item = &str> {
+        let results = match self {
+            RecipeResults::Single { .. } => &[][..],
+            RecipeResults::Multiple { results } => results.as_slice(),
+        };
+        results
+            .iter()
+            .filter(|result| result.item_type() == RecipeItemType::Fluid)
+            .map(|result| result.name())
+    }
+}
+
 const fn default_result_count() -> f64 {
     1.0
 }
@@ -277,11 +633,29 @@ pub enum RecipeIngredient {
     Tuple(RecipeIngredientTuple),
 }
 
-impl From<&RecipeIngredient> for (String, f64) {
+impl From<&RecipeIngredient> for (String, f64, RecipeItemType) {
     fn from(ri: &RecipeIngredient) -> Self {
         match &ri {
-            RecipeIngredient::Struct(sri) => (sri.name.clone(), (&sri.amount).into()),
-            RecipeIngredient::Tuple(tri) => (tri.0.clone(), tri.1),
+            RecipeIngredient::Struct(sri) => {
+                (sri.name.clone(), (&sri.amount).into(), sri.ingr_type)
+            }
+            RecipeIngredient::Tuple(tri) => (tri.0.clone(), tri.1, RecipeItemType::Item),
+        }
+    }
+}
+
+impl RecipeIngredient {
+    pub fn name(&self) -> &str {
+        match self {
+            RecipeIngredient::Struct(sri) => &sri.name,
+            RecipeIngredient::Tuple(tri) => &tri.0,
+        }
+    }
+
+    pub fn item_type(&self) -> RecipeItemType {
+        match self {
+            RecipeIngredient::Struct(sri) => sri.ingr_type,
+            RecipeIngredient::Tuple(_) => RecipeItemType::Item,
         }
     }
 }
@@ -319,6 +693,22 @@ impl From<&RecipeResult> for (String, f64) {
     }
 }
 
+impl RecipeResult {
+    pub fn name(&self) -> &str {
+        match self {
+            RecipeResult::Tuple(trr) => &trr.0,
+            RecipeResult::Struct(srr) => &srr.name,
+        }
+    }
+
+    pub fn item_type(&self) -> RecipeItemType {
+        match self {
+            RecipeResult::Tuple(_) => RecipeItemType::Item,
+            RecipeResult::Struct(srr) => srr.result_type,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RecipeResultTuple(String, f64);
 
@@ -332,24 +722,25 @@ pub struct RecipeResultStruct {
     amount: RecipeAmount,
 }
 
+/// Untagged variants are tried in order, so the ones with more fields have to come first
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RecipeAmount {
-    NamedNumber {
-        amount: f64,
-    },
-    MinMax {
+    MinMaxProbability {
         amount_min: f64,
         amount_max: f64,
+        probability: f64,
     },
     Probability {
         amount: f64,
         probability: f64,
     },
-    MinMaxProbability {
+    MinMax {
         amount_min: f64,
         amount_max: f64,
-        probability: f64,
+    },
+    NamedNumber {
+        amount: f64,
     },
 }
 
@@ -391,6 +782,34 @@ pub struct AssemblingMachine {
     #[serde(default = "Vec::new")]
     pub allowed_effects: Vec<EffectType>,
     pub module_specification: Option<ModuleSpec>,
+    #[serde(default, deserialize_with = "deserialize_energy")]
+    pub energy_usage: f64,
+    pub energy_source: Option<EnergySource>,
+}
+
+impl AssemblingMachine {
+    /// Crafting machines without an explicit drain idle at 1/30 of their usage
+    pub fn drain(&self) -> f64 {
+        match &self.energy_source {
+            Some(source) if source.source_type == EnergySourceType::Electric => {
+                source.drain.unwrap_or(self.energy_usage / 30.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn module_slots(&self) -> usize {
+        self.module_specification
+            .as_ref()
+            .map_or(0, |spec| spec.module_slots)
+    }
+
+    pub fn accepts_module(&self, module: &Module) -> bool {
+        module
+            .effect
+            .keys()
+            .all(|effect| self.allowed_effects.contains(effect))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -402,6 +821,24 @@ pub struct MiningDrill {
     pub resource_categories: Vec<String>,
     //pub allowed_effects: Option<EffectType>, // Exported data is broken a bit
     pub module_specification: Option<ModuleSpec>,
+    #[serde(default, deserialize_with = "deserialize_energy")]
+    pub energy_usage: f64,
+    pub energy_source: Option<EnergySource>,
+}
+
+impl MiningDrill {
+    pub fn drain(&self) -> f64 {
+        self.energy_source
+            .as_ref()
+            .and_then(|source| source.drain)
+            .unwrap_or(0.0)
+    }
+
+    pub fn module_slots(&self) -> usize {
+        self.module_specification
+            .as_ref()
+            .map_or(0, |spec| spec.module_slots)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -415,6 +852,36 @@ pub struct Resource {
     pub fluid_requirement: Option<FluidRequirement>,
     #[serde(flatten)]
     pub results: RecipeResults,
+    /// Infinite resources like crude oil are depleted down to a minimum yield instead of running out
+    #[serde(default)]
+    pub infinite: bool,
+    /// Amount that gives 100% yield on infinite resources
+    #[serde(default)]
+    pub normal: Option<f64>,
+    #[serde(default)]
+    pub minimum: Option<f64>,
+}
+
+impl Resource {
+    /// Lowest yield of an infinite resource as a fraction of its normal yield
+    pub fn minimum_yield(&self) -> f64 {
+        match (self.minimum, self.normal) {
+            (Some(minimum), Some(normal)) if normal > 0.0 => minimum / normal,
+            _ => 0.0,
+        }
+    }
+
+    /// Fluid results and the fluid needed for mining
+    pub fn fluids(&self) -> Vec<&str> {
+        self.results
+            .fluids()
+            .chain(
+                self.fluid_requirement
+                    .iter()
+                    .map(|requirement| requirement.required_fluid.as_str()),
+            )
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -430,6 +897,390 @@ pub struct OffshorePump {
     pub name: String,
     pub fluid: String,
     pub pumping_speed: f64,
+    #[serde(default, deserialize_with = "deserialize_energy")]
+    pub energy_usage: f64,
+    pub energy_source: Option<EnergySource>,
+}
+
+impl OffshorePump {
+    /// Pumping speed is given per tick
+    pub fn fluid_per_sec(&self) -> f64 {
+        self.pumping_speed * 60.0
+    }
+}
+
+/// Boilers and heat exchangers turn their energy into heated fluid
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Boiler {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_energy")]
+    pub energy_consumption: f64,
+    pub energy_source: EnergySource,
+    pub target_temperature: f64,
+    #[serde(default)]
+    pub fluid_box: FluidBox,
+    #[serde(default)]
+    pub output_fluid_box: FluidBox,
+}
+
+impl Boiler {
+    pub fn input_fluid(&self) -> String {
+        self.fluid_box
+            .filter
+            .clone()
+            .unwrap_or_else(|| "water".into())
+    }
+
+    pub fn output_fluid(&self) -> String {
+        self.output_fluid_box
+            .filter
+            .clone()
+            .unwrap_or_else(|| "steam".into())
+    }
+
+    pub fn fluid_per_sec(&self) -> f64 {
+        self.energy_consumption
+            / ((self.target_temperature - FLUID_BASE_TEMPERATURE) * FLUID_HEAT_CAPACITY)
+    }
+}
+
+/// Steam engines and turbines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Generator {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub fluid_usage_per_tick: f64,
+    pub maximum_temperature: f64,
+    #[serde(default = "default_effectivity")]
+    pub effectivity: f64,
+    #[serde(default)]
+    pub fluid_box: FluidBox,
+}
+
+impl Generator {
+    pub fn fluid(&self) -> String {
+        self.fluid_box
+            .filter
+            .clone()
+            .unwrap_or_else(|| "steam".into())
+    }
+
+    pub fn fluid_per_sec(&self) -> f64 {
+        self.fluid_usage_per_tick * 60.0
+    }
+
+    /// Power output in watts when fed with fluid at the maximum temperature
+    pub fn power_output(&self) -> f64 {
+        self.fluid_per_sec()
+            * (self.maximum_temperature - FLUID_BASE_TEMPERATURE)
+            * FLUID_HEAT_CAPACITY
+            * self.effectivity
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reactor {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_energy")]
+    pub consumption: f64,
+    pub energy_source: EnergySource,
+    #[serde(default)]
+    pub neighbour_bonus: f64,
+}
+
+/// Rocket silos also craft rocket parts as assembling machines, this is the launch part of them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RocketSilo {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub rocket_parts_required: f64,
+    pub fixed_recipe: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lab {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    #[serde(default = "default_researching_speed")]
+    pub researching_speed: f64,
+    pub inputs: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_energy")]
+    pub energy_usage: f64,
+    pub energy_source: Option<EnergySource>,
+}
+
+const fn default_researching_speed() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Technology {
+    pub name: String,
+    #[serde(flatten)]
+    pub technology_data: TechnologyBody,
+}
+
+impl Technology {
+    pub fn unit(&self, difficulty: Difficulty) -> &TechnologyUnit {
+        match &self.technology_data {
+            TechnologyBody::Simple { data } => &data.unit,
+            TechnologyBody::NormalAndExpensive { normal, expensive } => match difficulty {
+                Difficulty::Normal => &normal.unit,
+                Difficulty::Expensive => &expensive.unit,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TechnologyBody {
+    Simple {
+        #[serde(flatten)]
+        data: TechnologyData,
+    },
+    NormalAndExpensive {
+        normal: TechnologyData,
+        expensive: TechnologyData,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TechnologyData {
+    pub unit: TechnologyUnit,
+}
+
+/// Cost of one research unit, the number of units doesn't change the throughput
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TechnologyUnit {
+    pub ingredients: Vec<RecipeIngredient>,
+    pub time: f64,
+}
+
+impl TechnologyUnit {
+    pub fn ingredients(&self) -> Vec<(String, f64)> {
+        self.ingredients
+            .iter()
+            .map(Into::into)
+            .map(|(name, amount, _): (String, f64, RecipeItemType)| (name, amount))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FluidBox {
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergySource {
+    #[serde(rename = "type")]
+    pub source_type: EnergySourceType,
+    #[serde(default, deserialize_with = "deserialize_optional_energy")]
+    pub drain: Option<f64>,
+    #[serde(default)]
+    pub emissions_per_minute: f64,
+    #[serde(default = "default_effectivity")]
+    pub effectivity: f64,
+    pub fuel_category: Option<String>,
+    #[serde(default)]
+    pub fuel_categories: Vec<String>,
+}
+
+impl EnergySource {
+    /// Burner sources use chemical fuel unless told otherwise
+    pub fn fuel_categories(&self) -> Vec<String> {
+        if !self.fuel_categories.is_empty() {
+            self.fuel_categories.clone()
+        } else {
+            vec![self
+                .fuel_category
+                .clone()
+                .unwrap_or_else(|| "chemical".into())]
+        }
+    }
+}
+
+const fn default_effectivity() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnergySourceType {
+    Electric,
+    Burner,
+    Heat,
+    Fluid,
+    Void,
+}
+
+/// Energy is written with units in the game data, like "150kW".
+/// Processed data stores plain numbers in watts or joules.
+fn deserialize_energy<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    RawEnergy::deserialize(deserializer)?.value()
+}
+
+fn deserialize_optional_energy<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    Option::<RawEnergy>::deserialize(deserializer)?
+        .map(RawEnergy::value)
+        .transpose()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEnergy {
+    Number(f64),
+    Text(String),
+}
+
+impl RawEnergy {
+    fn value<E: Error>(self) -> Result<f64, E> {
+        match self {
+            RawEnergy::Number(value) => Ok(value),
+            RawEnergy::Text(text) => {
+                parse_energy(&text).ok_or_else(|| E::custom(format!("invalid energy {}", text)))
+            }
+        }
+    }
+}
+
+pub fn parse_energy(text: &str) -> Option<f64> {
+    let unit_start = text.find(|c: char| c.is_ascii_alphabetic())?;
+    let (value, unit) = text.split_at(unit_start);
+    let value: f64 = value.trim().parse().ok()?;
+    let multiplier = match unit {
+        "W" | "J" => 1.0,
+        "kW" | "kJ" | "KW" | "KJ" => 1e3,
+        "MW" | "MJ" => 1e6,
+        "GW" | "GJ" => 1e9,
+        "TW" | "TJ" => 1e12,
+        "PW" | "PJ" => 1e15,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Beacon {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub distribution_effectivity: f64,
+    #[serde(default = "Vec::new")]
+    pub allowed_effects: Vec<EffectType>,
+    pub module_specification: Option<ModuleSpec>,
+}
+
+impl Beacon {
+    pub fn module_slots(&self) -> usize {
+        self.module_specification
+            .as_ref()
+            .map_or(0, |spec| spec.module_slots)
+    }
+
+    pub fn accepts_module(&self, module: &Module) -> bool {
+        module
+            .effect
+            .keys()
+            .all(|effect| self.allowed_effects.contains(effect))
+    }
+}
+
+// Every tile of a belt holds 4 items on each of its 2 lanes, speed is in tiles per tick
+const BELT_ITEMS_PER_TILE: f64 = 8.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransportBelt {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub speed: f64,
+}
+
+impl TransportBelt {
+    /// Items per second carried by both lanes of a fully compressed belt
+    pub fn throughput(&self) -> f64 {
+        self.speed * 60.0 * BELT_ITEMS_PER_TILE
+    }
+}
+
+/// Offshore pumps and recipes like water wells that can supply a fluid
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaterSources<'a> {
+    pub fluid: String,
+    pub offshore_pumps: Vec<&'a OffshorePump>,
+    pub recipes: Vec<&'a Recipe>,
+}
+
+/// Rotation speed is in turns per tick, picking up and dropping items takes one full turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inserter {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub rotation_speed: f64,
+    /// Stack inserters get the stack inserter capacity bonus instead of the regular one
+    #[serde(default)]
+    pub stack: bool,
+}
+
+impl Inserter {
+    /// Items per second moved when every swing carries `hand_size` items
+    pub fn items_per_sec(&self, hand_size: u32) -> f64 {
+        self.rotation_speed * 60.0 * hand_size as f64
+    }
+}
+
+/// Speed is in tiles per tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogisticRobot {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub speed: f64,
+    pub max_payload_size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CargoWagon {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    /// Number of item stacks the wagon holds
+    pub inventory_size: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FluidWagon {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub capacity: f64,
+}
+
+/// Pump placed on a pipeline, pumping speed is in fluid units per tick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pump {
+    #[serde(alias = "icons")]
+    pub icon: Icon,
+    pub name: String,
+    pub pumping_speed: f64,
+}
+
+impl Pump {
+    pub fn fluid_per_sec(&self) -> f64 {
+        self.pumping_speed * 60.0
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -447,6 +1298,19 @@ pub struct Module {
     pub limitation: Option<Vec<String>>,
 }
 
+impl Module {
+    pub fn bonus(&self, effect: EffectType) -> f64 {
+        self.effect.get(&effect).map_or(0.0, |e| e.bonus)
+    }
+
+    /// Productivity modules are limited to intermediate products
+    pub fn allowed_in_recipe(&self, recipe: &str) -> bool {
+        self.limitation
+            .as_ref()
+            .is_none_or(|limitation| limitation.iter().any(|r| r == recipe))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EffectType {
@@ -471,3 +1335,43 @@ pub struct ItemSubGroup {
     pub name: String,
     pub group: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probability_is_kept() {
+        let result: RecipeResult =
+            serde_json::from_str(r#"{"name": "uranium-235", "probability": 0.007, "amount": 1}"#)
+                .unwrap();
+        let (name, amount): (String, f64) = (&result).into();
+        assert_eq!(name, "uranium-235");
+        assert!((amount - 0.007).abs() < 1e-12);
+        let processed = serde_json::to_string(&result).unwrap();
+        assert!(processed.contains("probability"));
+    }
+
+    #[test]
+    fn min_max_probability_is_kept() {
+        let result: RecipeResult = serde_json::from_str(
+            r#"{"name": "wood", "amount_min": 2, "amount_max": 4, "probability": 0.5}"#,
+        )
+        .unwrap();
+        let (_, amount): (String, f64) = (&result).into();
+        assert!((amount - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fluid_ingredient_stays_a_fluid() {
+        let ingredient: RecipeIngredient =
+            serde_json::from_str(r#"{"type": "fluid", "name": "water", "amount": 10}"#).unwrap();
+        let (name, amount, item_type): (String, f64, RecipeItemType) = (&ingredient).into();
+        assert_eq!(name, "water");
+        assert_eq!(amount, 10.0);
+        assert_eq!(item_type, RecipeItemType::Fluid);
+        let item: RecipeIngredient = serde_json::from_str(r#"["iron-plate", 2]"#).unwrap();
+        let (_, _, item_type): (String, f64, RecipeItemType) = (&item).into();
+        assert_eq!(item_type, RecipeItemType::Item);
+    }
+}
@@ -1,11 +1,11 @@
 mod components;
 mod constants;
-mod data;
 mod icon_map;
 mod prototype_ref;
 
 use components::*;
 use data::GameData;
+use factorio_web_calculator::{data, solver};
 use gloo_net::http::Request;
 use icon_map::IconMap;
 use std::{ops::Deref, rc::Rc, sync::RwLock};
@@ -1,4 +1,4 @@
-use factorio_web_calculator::data::{AssemblingMachine, GameData, MiningDrill};
+use factorio_web_calculator::data::{AssemblingMachine, GameData, MiningDrill, Module, Recipe};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AssemblingMachineRef {
@@ -37,3 +37,41 @@ impl MiningDrillRef {
         Self { name }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecipeRef {
+    name: String,
+}
+
+impl RecipeRef {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_full<'a>(&self, game_data: &'a GameData) -> Option<&'a Recipe> {
+        game_data.recipes.get(&self.name)
+    }
+
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleRef {
+    name: String,
+}
+
+impl ModuleRef {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_full<'a>(&self, game_data: &'a GameData) -> Option<&'a Module> {
+        game_data.modules.get(&self.name)
+    }
+
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
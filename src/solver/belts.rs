@@ -0,0 +1,63 @@
+use crate::data::*;
+
+/// Counts belts of one tier needed to carry item flows, fluids and energy are not carried by belts
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeltCounter<'a> {
+    pub belt: &'a TransportBelt,
+    game_data: &'a GameData,
+}
+
+impl<'a> BeltCounter<'a> {
+    /// Uses the named belt, or the slowest one if there's no belt with that name
+    pub fn new(game_data: &'a GameData, belt: &str) -> Option<Self> {
+        let belt = game_data
+            .transport_belts
+            .get(belt)
+            .or_else(|| game_data.belts_sorted().into_iter().next())?;
+        Some(Self { belt, game_data })
+    }
+
+    pub fn is_carried(&self, item: &str) -> bool {
+        self.game_data.is_solid_item(item)
+    }
+
+    /// Belts needed for a flow in items per second, `None` if the item is not carried by belts
+    pub fn belts(&self, item: &str, rate: f64) -> Option<f64> {
+        self.is_carried(item).then(|| rate / self.belt.throughput())
+    }
+
+    pub fn lanes(&self, item: &str, rate: f64) -> Option<f64> {
+        self.belts(item, rate).map(|belts| belts * 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::test_data::logistics_game_data;
+
+    #[test]
+    fn counts_belts_and_lanes_of_the_named_belt() {
+        let game_data = logistics_game_data();
+        let belts = BeltCounter::new(&game_data, "fast-transport-belt").unwrap();
+        assert_eq!(belts.belts("iron-plate", 15.0), Some(0.5));
+        assert_eq!(belts.lanes("iron-plate", 15.0), Some(1.0));
+    }
+
+    #[test]
+    fn unknown_belt_falls_back_to_the_slowest() {
+        let game_data = logistics_game_data();
+        let belts = BeltCounter::new(&game_data, "express-transport-belt").unwrap();
+        assert_eq!(belts.belt.name, "transport-belt");
+        assert_eq!(belts.belts("stone", 15.0), Some(1.0));
+    }
+
+    #[test]
+    fn fluids_and_energy_are_not_carried() {
+        let game_data = logistics_game_data();
+        let belts = BeltCounter::new(&game_data, "transport-belt").unwrap();
+        assert_eq!(belts.belts("water", 100.0), None);
+        assert_eq!(belts.belts(ELECTRIC_ENERGY, 100.0), None);
+        assert_eq!(belts.belts(HEAT_ENERGY, 100.0), None);
+    }
+}
@@ -0,0 +1,60 @@
+use super::{Factory, InserterCounter, Machine};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcStep<'a> {
+    pub factory: Factory<'a>,
+    pub amount: f64,
+}
+
+impl CalcStep<'_> {
+    pub fn produced_per_sec(&self) -> Vec<(String, f64)> {
+        self.factory
+            .produced_per_sec()
+            .into_iter()
+            .map(|(name, amount)| (name, amount * self.amount))
+            .collect()
+    }
+
+    pub fn consumed_per_sec(&self) -> Vec<(String, f64)> {
+        self.factory
+            .consumed_per_sec()
+            .into_iter()
+            .map(|(name, amount)| (name, amount * self.amount))
+            .collect()
+    }
+
+    /// Electric power in watts, every started machine pays its drain
+    /// `None` when the game data has no energy for the machine
+    pub fn power_usage(&self) -> Option<f64> {
+        self.factory.has_energy_data().then(|| {
+            self.factory.power_usage() * self.amount + self.factory.drain() * self.amount.ceil()
+        })
+    }
+
+    /// `None` when the game data has no emissions for the machine
+    pub fn pollution_per_minute(&self) -> Option<f64> {
+        self.factory
+            .has_energy_data()
+            .then(|| self.factory.pollution_per_minute() * self.amount)
+    }
+
+    /// Inserters each machine needs to keep up with its inputs and outputs, every item gets its own inserters.
+    /// Mining drills put their products out without inserters
+    pub fn inserters_per_machine(&self, inserters: &InserterCounter) -> usize {
+        let produced = match self.factory.machine {
+            Machine::MiningDrill(_, _) => vec![],
+            _ => self.factory.produced_per_sec(),
+        };
+        self.factory
+            .consumed_per_sec()
+            .into_iter()
+            .chain(produced)
+            .filter_map(|(name, rate)| inserters.inserters(&name, rate))
+            .map(|count| count.ceil() as usize)
+            .sum()
+    }
+
+    pub fn machine_name(&self) -> String {
+        self.factory.name()
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::CalcTargetRate;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalcTarget {
+    pub name: String,
+    pub rate: CalcTargetRate,
+}
+
+impl CalcTarget {
+    pub fn new(name: impl Into<String>, rate: CalcTargetRate) -> Self {
+        Self {
+            name: name.into(),
+            rate,
+        }
+    }
+}
@@ -0,0 +1,625 @@
+use super::{
+    BeaconSetting, CalcStep, CalcTarget, ConstraintKind, Factory, LinearProgram, Machine,
+    ProductionGraph, SimplexError, SolverSettings, RELATIVE_TOLERANCE, VERY_SMALL,
+};
+use crate::data::GameData;
+use hashbrown::{HashMap, HashSet};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+#[derive(Debug, Clone)]
+pub struct Calculation<'a> {
+    game_data: &'a GameData,
+    settings: SolverSettings,
+    vector: HashMap<String, f64>,
+    outputs: Vec<(String, f64)>,
+    imported_items: HashSet<String>,
+    input_limits: HashMap<String, f64>,
+    module_overrides: HashMap<String, Vec<String>>,
+    beacon_overrides: HashMap<String, BeaconSetting>,
+    pub steps: HashMap<Factory<'a>, f64>,
+}
+
+impl<'a> Calculation<'a> {
+    pub fn new(game_data: &'a GameData, settings: SolverSettings) -> Self {
+        Self {
+            game_data,
+            settings,
+            vector: HashMap::new(),
+            outputs: Vec::new(),
+            imported_items: HashSet::new(),
+            input_limits: HashMap::new(),
+            module_overrides: HashMap::new(),
+            beacon_overrides: HashMap::new(),
+            steps: HashMap::new(),
+        }
+    }
+
+    /// Imported items are supplied from outside and are reported as inputs instead of being produced
+    pub fn with_imported_items(mut self, items: impl IntoIterator<Item = String>) -> Self {
+        self.imported_items.extend(items);
+        self
+    }
+
+    /// Items supplied from outside up to a rate in items per second, they are reported as inputs like imported items
+    pub fn with_input_limits(mut self, limits: impl IntoIterator<Item = (String, f64)>) -> Self {
+        self.input_limits.extend(limits);
+        self
+    }
+
+    /// Modules chosen for individual steps, keyed by recipe or resource name
+    pub fn with_module_overrides(mut self, overrides: HashMap<String, Vec<String>>) -> Self {
+        self.module_overrides.extend(overrides);
+        self
+    }
+
+    /// Beacons chosen for individual steps, keyed by recipe name
+    pub fn with_beacon_overrides(mut self, overrides: HashMap<String, BeaconSetting>) -> Self {
+        self.beacon_overrides.extend(overrides);
+        self
+    }
+
+    pub fn solve(self, input: &[CalcTarget]) -> Result<Self, CalculationError> {
+        let rates = self.target_rates(input)?;
+        self.solve_rates(input, rates)
+    }
+
+    /// Targets are made as fast as the input limits allow, keeping the ratio between their rates
+    pub fn maximize(self, input: &[CalcTarget]) -> Result<Self, CalculationError> {
+        let rates = self.target_rates(input)?;
+        let factories = self.collect_factories(input)?;
+        let items = self.collect_items(&factories);
+
+        // The last column scales the rates of the targets
+        let mut objective = vec![0.0; factories.len()];
+        objective.push(1.0);
+        let mut lp = LinearProgram::new(objective);
+        for item in &items {
+            let mut coefficients = Self::net_production(&factories, item);
+            coefficients.push(-Self::demand(&rates, item));
+            lp.add_constraint(
+                coefficients,
+                ConstraintKind::GreaterOrEqual,
+                -self.limit(item),
+            );
+        }
+
+        let scale = match lp.maximize() {
+            Ok(values) => values[factories.len()],
+            Err(SimplexError::Unbounded) => return Err(CalculationError::NotLimited),
+            Err(why) => return Err(why.into()),
+        };
+        log::info!("Targets can be made at {:.3} times their rates", scale);
+        let rates = rates
+            .into_iter()
+            .map(|(name, rate)| (name, rate * scale))
+            .collect();
+        self.solve_rates(input, rates)
+    }
+
+    /// Requested rates of the targets in items per second
+    fn target_rates(&self, input: &[CalcTarget]) -> Result<Vec<(String, f64)>, CalculationError> {
+        input
+            .iter()
+            .map(|target| {
+                let factory = self.factory_for_item(&target.name)?;
+                let rate = target
+                    .rate
+                    .as_ips(factory.item_net_produced_per_sec(&target.name));
+                Ok((target.name.clone(), rate))
+            })
+            .collect()
+    }
+
+    fn solve_rates(
+        mut self,
+        input: &[CalcTarget],
+        rates: Vec<(String, f64)>,
+    ) -> Result<Self, CalculationError> {
+        for (name, items_per_second) in &rates {
+            let val = self.vector.entry(name.clone()).or_insert(0.0);
+            *val -= items_per_second;
+            self.outputs.push((name.clone(), *items_per_second));
+        }
+
+        let factories = self.collect_factories(input)?;
+        let items = self.collect_items(&factories);
+        log::info!(
+            "Solving for {} items using {} factories",
+            items.len(),
+            factories.len()
+        );
+
+        // Every column is a factory, every row is an item. Net production of every item must cover the demand,
+        // limited items may be consumed up to their limit.
+        let mut lp = LinearProgram::new(vec![1.0; factories.len()]);
+        for item in &items {
+            lp.add_constraint(
+                Self::net_production(&factories, item),
+                ConstraintKind::GreaterOrEqual,
+                Self::demand(&rates, item) - self.limit(item),
+            );
+        }
+
+        let amounts = lp.minimize()?;
+        for (factory, amount) in factories.into_iter().zip(amounts) {
+            if amount > VERY_SMALL {
+                self.apply_step(CalcStep { factory, amount });
+            }
+        }
+
+        Ok(self)
+    }
+
+    fn net_production(factories: &[Factory], item: &str) -> Vec<f64> {
+        factories
+            .iter()
+            .map(|factory| factory.item_net_produced_per_sec(item))
+            .collect()
+    }
+
+    fn demand(rates: &[(String, f64)], item: &str) -> f64 {
+        rates
+            .iter()
+            .filter(|(name, _)| name == item)
+            .map(|(_, rate)| rate)
+            .sum()
+    }
+
+    fn limit(&self, item: &str) -> f64 {
+        self.input_limits.get(item).copied().unwrap_or(0.0)
+    }
+
+    /// Imported and limited items are not produced by any step
+    fn is_supplied(&self, item: &str) -> bool {
+        self.imported_items.contains(item) || self.input_limits.contains_key(item)
+    }
+
+    /// Limited inputs that are used up completely, these stop the targets from growing
+    pub fn binding_limits(&self) -> Vec<(String, f64)> {
+        Self::sorted(
+            self.input_limits
+                .iter()
+                .filter(|(name, limit)| {
+                    let used = -self.vector.get(*name).copied().unwrap_or(0.0);
+                    used >= **limit - (**limit * RELATIVE_TOLERANCE).max(VERY_SMALL)
+                })
+                .map(|(name, limit)| (name.clone(), *limit))
+                .collect(),
+        )
+    }
+
+    /// Items that have to be fed into the factory, like ores, water, crude oil and imported items
+    pub fn inputs(&self) -> Vec<(String, f64)> {
+        let mut inputs: HashMap<String, f64> = HashMap::new();
+        for (factory, amount) in &self.steps {
+            if let Machine::MiningDrill(_, _) | Machine::OffshorePump(_) = factory.machine {
+                for (name, produced) in factory.produced_per_sec() {
+                    *inputs.entry(name).or_insert(0.0) += produced * amount;
+                }
+            }
+        }
+        for (name, balance) in &self.vector {
+            if *balance < -VERY_SMALL {
+                *inputs.entry(name.clone()).or_insert(0.0) -= balance;
+            }
+        }
+        Self::sorted(inputs.into_iter().collect())
+    }
+
+    /// Byproducts that are produced in excess and have to be stored or voided
+    pub fn surplus(&self) -> Vec<(String, f64)> {
+        let outputs: HashSet<&String> = self.outputs.iter().map(|(name, _)| name).collect();
+        Self::sorted(
+            self.vector
+                .iter()
+                .filter(|(name, balance)| **balance > VERY_SMALL && !outputs.contains(name))
+                .map(|(name, balance)| (name.clone(), *balance))
+                .collect(),
+        )
+    }
+
+    /// Requested targets, in items per second
+    pub fn outputs(&self) -> Vec<(String, f64)> {
+        let mut outputs: HashMap<String, f64> = HashMap::new();
+        for (name, amount) in &self.outputs {
+            *outputs.entry(name.clone()).or_insert(0.0) += amount;
+        }
+        // Targets can be overproduced when they are also a byproduct of another step
+        for (name, amount) in outputs.iter_mut() {
+            *amount += self.vector.get(name).copied().unwrap_or(0.0).max(0.0);
+        }
+        Self::sorted(outputs.into_iter().collect())
+    }
+
+    /// Fluids produced or consumed by any of the steps
+    pub fn fluids(&self) -> HashSet<String> {
+        self.steps
+            .keys()
+            .flat_map(|factory| factory.fluids())
+            .collect()
+    }
+
+    /// Electric power used by all steps, in watts. `None` when the energy of any machine is unknown
+    pub fn power_usage(&self) -> Option<f64> {
+        self.calc_steps().map(|step| step.power_usage()).sum()
+    }
+
+    /// `None` when the emissions of any machine are unknown
+    pub fn pollution_per_minute(&self) -> Option<f64> {
+        self.calc_steps()
+            .map(|step| step.pollution_per_minute())
+            .sum()
+    }
+
+    fn calc_steps(&self) -> impl Iterator<Item = CalcStep<'a>> + '_ {
+        self.steps.iter().map(|(factory, amount)| CalcStep {
+            factory: factory.clone(),
+            amount: *amount,
+        })
+    }
+
+    pub fn graph(&self) -> ProductionGraph<'a> {
+        ProductionGraph::new(self.calc_steps().collect())
+    }
+
+    fn sorted(mut items: Vec<(String, f64)>) -> Vec<(String, f64)> {
+        items.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+        items
+    }
+
+    fn factory_for_item(&self, item: &str) -> Result<Factory<'a>, CalculationError> {
+        let factory = Factory::for_item(self.game_data, &self.settings, item)?;
+        let factory = match self.beacon_overrides.get(&factory.recipe_name()) {
+            Some(setting) => factory.apply_beacon_setting(self.game_data, setting),
+            None => factory,
+        };
+        Ok(match self.module_overrides.get(&factory.recipe_name()) {
+            Some(modules) => factory.with_modules(
+                modules
+                    .iter()
+                    .filter_map(|name| self.game_data.modules.get(name))
+                    .collect(),
+            ),
+            None => factory,
+        })
+    }
+
+    fn collect_factories(
+        &self,
+        input: &[CalcTarget],
+    ) -> Result<Vec<Factory<'a>>, CalculationError> {
+        let mut factories = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<String> = input.iter().map(|t| t.name.clone()).collect();
+        while let Some(item) = queue.pop_front() {
+            if self.is_supplied(&item) || !visited.insert(item.clone()) {
+                continue;
+            }
+            let factory = self.factory_for_item(&item)?;
+            queue.extend(factory.consumed_per_sec().into_iter().map(|(name, _)| name));
+            if !factories.contains(&factory) {
+                factories.push(factory);
+            }
+        }
+        Ok(factories)
+    }
+
+    /// Items that need a balance row, imported items can be consumed without limits
+    fn collect_items(&self, factories: &[Factory]) -> Vec<String> {
+        let mut items: Vec<String> = Vec::new();
+        for factory in factories {
+            for (name, _) in factory
+                .produced_per_sec()
+                .into_iter()
+                .chain(factory.consumed_per_sec())
+            {
+                if !items.contains(&name) && !self.imported_items.contains(&name) {
+                    items.push(name);
+                }
+            }
+        }
+        items
+    }
+
+    pub fn apply_step(&mut self, step: CalcStep<'a>) {
+        log::info!("Applying step in amount {:.3}", step.amount);
+        let produced = step.produced_per_sec();
+        let consumed = step.consumed_per_sec();
+
+        for (name, amount) in &produced {
+            log::info!("ingredient {} produced in amount of {:.3}", name, amount);
+            let val = self.vector.entry(name.clone()).or_insert(0.0);
+            *val += amount;
+        }
+
+        for (name, amount) in &consumed {
+            log::info!("ingredient {} consumed in amount of {:.3}", name, amount);
+            let val = self.vector.entry(name.clone()).or_insert(0.0);
+            *val -= amount;
+        }
+        let step_entry = self.steps.entry(step.factory).or_insert(0.0);
+        *step_entry += step.amount;
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CalculationError {
+    #[error("Recipe or Resource for item {0} not found")]
+    RecipeOrResourceNotFound(String),
+    #[error("Assembling machine for recipe {0} not found")]
+    AssemblingMachineNotFound(String),
+    #[error("Mining Drill for resource {0} not found")]
+    MiningDrillNotFound(String),
+    #[error("None of the input limits restrict the targets")]
+    NotLimited,
+    #[error("Failed to solve: {0}")]
+    Solver(#[from] SimplexError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{test_data, CalcTargetRate};
+    use serde_json::json;
+
+    fn game_data() -> GameData {
+        test_data::game_data(json!({
+            "items": {
+                "iron-ore": {"icon": "iron-ore.png", "name": "iron-ore", "stack_size": 50},
+                "iron-plate": {"icon": "iron-plate.png", "name": "iron-plate", "stack_size": 100},
+                "iron-gear-wheel": {"icon": "iron-gear-wheel.png", "name": "iron-gear-wheel", "stack_size": 100},
+                "uranium-ore": {"icon": "uranium-ore.png", "name": "uranium-ore", "stack_size": 50},
+                "uranium-235": {"icon": "uranium-235.png", "name": "uranium-235", "stack_size": 100},
+                "uranium-238": {"icon": "uranium-238.png", "name": "uranium-238", "stack_size": 100}
+            },
+            "recipes": {
+                "iron-plate": {
+                    "name": "iron-plate",
+                    "category": "smelting",
+                    "energy_required": 1.0,
+                    "ingredients": [["iron-ore", 1.0]],
+                    "result": "iron-plate"
+                },
+                "iron-gear-wheel": {
+                    "name": "iron-gear-wheel",
+                    "energy_required": 1.0,
+                    "ingredients": [["iron-plate", 2.0]],
+                    "result": "iron-gear-wheel"
+                },
+                "uranium-processing": {
+                    "name": "uranium-processing",
+                    "category": "centrifuging",
+                    "energy_required": 1.0,
+                    "ingredients": [["uranium-ore", 10.0]],
+                    "result": "uranium-238"
+                },
+                "kovarex-enrichment-process": {
+                    "name": "kovarex-enrichment-process",
+                    "category": "centrifuging",
+                    "energy_required": 60.0,
+                    "ingredients": [["uranium-235", 40.0], ["uranium-238", 5.0]],
+                    "results": [
+                        {"name": "uranium-235", "amount": 41.0},
+                        {"name": "uranium-238", "amount": 2.0}
+                    ]
+                }
+            },
+            "assembling_machines": {
+                "assembler": {
+                    "icon": "assembler.png",
+                    "name": "assembler",
+                    "crafting_categories": ["crafting", "smelting", "centrifuging"],
+                    "crafting_speed": 1.0
+                }
+            },
+            "mining_drills": {
+                "drill": {
+                    "icon": "drill.png",
+                    "name": "drill",
+                    "mining_speed": 1.0,
+                    "resource_categories": ["basic-solid"]
+                }
+            },
+            "resources": {
+                "iron-ore": {
+                    "icon": "iron-ore.png",
+                    "name": "iron-ore",
+                    "category": "basic-solid",
+                    "mining_time": 1.0,
+                    "result": "iron-ore"
+                },
+                "uranium-ore": {
+                    "icon": "uranium-ore.png",
+                    "name": "uranium-ore",
+                    "category": "basic-solid",
+                    "mining_time": 1.0,
+                    "result": "uranium-ore"
+                }
+            }
+        }))
+    }
+
+    /// Fixture machines with electric energy sources
+    fn electric_game_data() -> GameData {
+        let mut game_data = game_data();
+        let assembler = game_data.assembling_machines.get_mut("assembler").unwrap();
+        assembler.energy_usage = 150_000.0;
+        assembler.energy_source = Some(test_data::from_json(
+            json!({"type": "electric", "emissions_per_minute": 4.0}),
+        ));
+        let drill = game_data.mining_drills.get_mut("drill").unwrap();
+        drill.energy_usage = 90_000.0;
+        drill.energy_source = Some(test_data::from_json(
+            json!({"type": "electric", "emissions_per_minute": 10.0}),
+        ));
+        game_data
+    }
+
+    fn target(name: &str, rate: f64) -> CalcTarget {
+        CalcTarget::new(name, CalcTargetRate::ItemsPerSecond(rate))
+    }
+
+    fn machines(calculation: &Calculation, recipe: &str) -> f64 {
+        calculation
+            .steps
+            .iter()
+            .filter(|(factory, _)| factory.recipe_name() == recipe)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    fn assert_rates(actual: Vec<(String, f64)>, expected: &[(&str, f64)]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for ((name, rate), (expected_name, expected_rate)) in actual.iter().zip(expected) {
+            assert_eq!(name, expected_name);
+            assert!((rate - expected_rate).abs() < 1e-6, "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn single_recipe_ratio() {
+        let game_data = game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        assert!((machines(&calculation, "iron-gear-wheel") - 1.0).abs() < 1e-6);
+        assert!((machines(&calculation, "iron-plate") - 2.0).abs() < 1e-6);
+        assert!((machines(&calculation, "iron-ore") - 2.0).abs() < 1e-6);
+        assert_rates(calculation.inputs(), &[("iron-ore", 2.0)]);
+        assert_rates(calculation.outputs(), &[("iron-gear-wheel", 1.0)]);
+        assert!(calculation.surplus().is_empty());
+    }
+
+    #[test]
+    fn imported_item_is_an_input() {
+        let game_data = game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .with_imported_items(["iron-plate".to_string()])
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        assert_eq!(calculation.steps.len(), 1);
+        assert_rates(calculation.inputs(), &[("iron-plate", 2.0)]);
+    }
+
+    #[test]
+    fn limited_input_is_not_produced() {
+        let game_data = game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .with_input_limits([("iron-plate".to_string(), 3.0)])
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        assert_eq!(machines(&calculation, "iron-plate"), 0.0);
+        assert_rates(calculation.inputs(), &[("iron-plate", 2.0)]);
+        assert!(calculation.binding_limits().is_empty());
+    }
+
+    #[test]
+    fn input_limit_below_demand_is_infeasible() {
+        let game_data = game_data();
+        let result = Calculation::new(&game_data, SolverSettings::default())
+            .with_input_limits([("iron-plate".to_string(), 1.5)])
+            .solve(&[target("iron-gear-wheel", 1.0)]);
+        assert!(matches!(
+            result,
+            Err(CalculationError::Solver(SimplexError::Infeasible))
+        ));
+    }
+
+    #[test]
+    fn maximize_up_to_input_limit() {
+        let game_data = game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .with_input_limits([("iron-ore".to_string(), 4.0)])
+            .maximize(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        assert_rates(calculation.outputs(), &[("iron-gear-wheel", 2.0)]);
+        assert_rates(calculation.binding_limits(), &[("iron-ore", 4.0)]);
+    }
+
+    #[test]
+    fn maximize_without_limits_fails() {
+        let game_data = game_data();
+        let result = Calculation::new(&game_data, SolverSettings::default())
+            .maximize(&[target("iron-gear-wheel", 1.0)]);
+        assert!(matches!(result, Err(CalculationError::NotLimited)));
+    }
+
+    #[test]
+    fn recipe_loop_is_solved_by_its_net_effect() {
+        let game_data = game_data();
+        let mut settings = SolverSettings::default();
+        settings.disabled_recipes.clear();
+        settings
+            .item_recipes
+            .insert("uranium-238".into(), "uranium-processing".into());
+        let calculation = Calculation::new(&game_data, settings)
+            .solve(&[target("uranium-235", 1.0)])
+            .unwrap();
+        // Every enrichment turns 3 uranium-238 into 1 uranium-235
+        assert!((machines(&calculation, "kovarex-enrichment-process") - 60.0).abs() < 1e-6);
+        assert!((machines(&calculation, "uranium-processing") - 3.0).abs() < 1e-6);
+        assert_rates(calculation.inputs(), &[("uranium-ore", 30.0)]);
+        assert_rates(calculation.outputs(), &[("uranium-235", 1.0)]);
+    }
+
+    #[test]
+    fn recipe_loop_is_solved_with_default_recipes() {
+        let game_data = game_data();
+        let mut settings = SolverSettings::default();
+        settings.disabled_recipes.clear();
+        let calculation = Calculation::new(&game_data, settings.clone())
+            .solve(&[target("uranium-238", 1.0)])
+            .unwrap();
+        // Enrichment uses up uranium-238, so it's never picked to make it
+        assert_eq!(machines(&calculation, "kovarex-enrichment-process"), 0.0);
+        assert!((machines(&calculation, "uranium-processing") - 1.0).abs() < 1e-6);
+        let calculation = Calculation::new(&game_data, settings)
+            .solve(&[target("uranium-235", 1.0)])
+            .unwrap();
+        assert!((machines(&calculation, "kovarex-enrichment-process") - 60.0).abs() < 1e-6);
+        assert!((machines(&calculation, "uranium-processing") - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn loop_recipes_are_disabled_by_default() {
+        let game_data = game_data();
+        let result = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[target("uranium-235", 1.0)]);
+        assert!(matches!(
+            result,
+            Err(CalculationError::RecipeOrResourceNotFound(item)) if item == "uranium-235"
+        ));
+    }
+
+    #[test]
+    fn power_is_unknown_without_energy_sources() {
+        let game_data = game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        assert_eq!(calculation.power_usage(), None);
+        assert_eq!(calculation.pollution_per_minute(), None);
+    }
+
+    #[test]
+    fn power_includes_drain_of_started_machines() {
+        let game_data = electric_game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        // 3 assemblers and 2 drills working, 3 assemblers draining 1/30 of their usage
+        let power = calculation.power_usage().unwrap();
+        assert!((power - 645_000.0).abs() < 1e-6, "{}", power);
+    }
+
+    #[test]
+    fn pollution_of_all_machines() {
+        let game_data = electric_game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[target("iron-gear-wheel", 1.0)])
+            .unwrap();
+        let pollution = calculation.pollution_per_minute().unwrap();
+        assert!((pollution - 32.0).abs() < 1e-6, "{}", pollution);
+    }
+}
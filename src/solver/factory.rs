@@ -0,0 +1,1143 @@
+use std::hash::{Hash, Hasher};
+
+use crate::data::*;
+
+use super::{BeaconSetting, CalculationError, FactoryModules, SolverSettings};
+
+/// Machine or building that makes a step of the production
+#[derive(Debug, Clone, Copy)]
+pub enum Machine<'a> {
+    AssemblingMachine(&'a AssemblingMachine, &'a Recipe),
+    MiningDrill(&'a MiningDrill, &'a Resource),
+    OffshorePump(&'a OffshorePump),
+    Boiler(&'a Boiler),
+    Generator(&'a Generator),
+    Reactor(&'a Reactor),
+    /// Launches of a payload, the amount is rockets per minute. The recipe is the one fixed in the silo.
+    RocketLaunch(&'a RocketSilo, &'a Item, Option<&'a Recipe>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Factory<'a> {
+    pub machine: Machine<'a>,
+    /// Only assembling machines and mining drills have modules
+    pub modules: FactoryModules<'a>,
+    /// Fuel burnt by machines with burner energy sources
+    pub fuel: Option<&'a Item>,
+    pub difficulty: Difficulty,
+    /// Productivity bonus of mining drills from research
+    pub mining_productivity: f64,
+    /// Yield of an infinite resource, as a fraction of its normal yield
+    pub resource_yield: f64,
+}
+
+impl PartialEq for Factory<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        let machine_eq = match (self.machine, other.machine) {
+            (Machine::AssemblingMachine(am1, rec1), Machine::AssemblingMachine(am2, rec2)) => {
+                am1.name.eq(&am2.name) && rec1.name.eq(&rec2.name)
+            }
+            (Machine::MiningDrill(md1, res1), Machine::MiningDrill(md2, res2)) => {
+                md1.name.eq(&md2.name) && res1.name.eq(&res2.name)
+            }
+            (Machine::OffshorePump(op1), Machine::OffshorePump(op2)) => op1.name.eq(&op2.name),
+            (Machine::Boiler(b1), Machine::Boiler(b2)) => b1.name.eq(&b2.name),
+            (Machine::Generator(g1), Machine::Generator(g2)) => g1.name.eq(&g2.name),
+            (Machine::Reactor(r1), Machine::Reactor(r2)) => r1.name.eq(&r2.name),
+            (Machine::RocketLaunch(rs1, p1, _), Machine::RocketLaunch(rs2, p2, _)) => {
+                rs1.name.eq(&rs2.name) && p1.name.eq(&p2.name)
+            }
+            _ => false,
+        };
+        machine_eq
+            && self.modules.eq(&other.modules)
+            && self.fuel.map(|f| &f.name).eq(&other.fuel.map(|f| &f.name))
+            && self.difficulty.eq(&other.difficulty)
+            && self.mining_productivity.eq(&other.mining_productivity)
+            && self.resource_yield.eq(&other.resource_yield)
+    }
+}
+
+impl Eq for Factory<'_> {}
+
+impl Hash for Factory<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.machine {
+            Machine::AssemblingMachine(am, rec) => {
+                state.write_u8(1);
+                am.name.hash(state);
+                rec.name.hash(state);
+            }
+            Machine::MiningDrill(md, res) => {
+                state.write_u8(2);
+                md.name.hash(state);
+                res.name.hash(state);
+            }
+            Machine::OffshorePump(op) => {
+                state.write_u8(3);
+                op.name.hash(state)
+            }
+            Machine::Boiler(b) => {
+                state.write_u8(4);
+                b.name.hash(state)
+            }
+            Machine::Generator(g) => {
+                state.write_u8(5);
+                g.name.hash(state)
+            }
+            Machine::Reactor(r) => {
+                state.write_u8(6);
+                r.name.hash(state)
+            }
+            Machine::RocketLaunch(rs, payload, _) => {
+                state.write_u8(7);
+                rs.name.hash(state);
+                payload.name.hash(state)
+            }
+        }
+        self.modules.hash(state);
+        self.fuel.map(|f| &f.name).hash(state);
+        self.difficulty.hash(state);
+        self.mining_productivity.to_bits().hash(state);
+        self.resource_yield.to_bits().hash(state);
+    }
+}
+
+impl<'a> Factory<'a> {
+    pub fn new(machine: Machine<'a>, difficulty: Difficulty) -> Self {
+        Self {
+            machine,
+            modules: FactoryModules::default(),
+            fuel: None,
+            difficulty,
+            mining_productivity: 0.0,
+            resource_yield: 1.0,
+        }
+    }
+
+    pub fn produced_per_sec(&self) -> Vec<(String, f64)> {
+        match self.machine {
+            Machine::AssemblingMachine(_, re) => re
+                .produces(self.difficulty)
+                .into_iter()
+                .map(|(name, amount)| {
+                    (
+                        name,
+                        self.crafts_per_sec() * amount * self.modules.productivity_multiplier(),
+                    )
+                })
+                .collect(),
+            Machine::MiningDrill(_, re) => {
+                let temp: Vec<(String, f64)> = (&re.results).into();
+                let productivity =
+                    self.modules.productivity_multiplier() + self.mining_productivity;
+                temp.into_iter()
+                    .map(|(name, amount)| {
+                        (
+                            name,
+                            self.crafts_per_sec() * amount * productivity * self.resource_yield,
+                        )
+                    })
+                    .collect()
+            }
+            Machine::OffshorePump(op) => vec![(op.fluid.clone(), op.fluid_per_sec())],
+            Machine::Boiler(b) => vec![(b.output_fluid(), b.fluid_per_sec())],
+            Machine::Generator(g) => vec![(ELECTRIC_ENERGY.into(), g.power_output() / ENERGY_UNIT)],
+            // Reactors are assumed to have no neighbours
+            Machine::Reactor(r) => vec![(HEAT_ENERGY.into(), r.consumption / ENERGY_UNIT)],
+            Machine::RocketLaunch(_, payload, _) => payload
+                .launch_products()
+                .into_iter()
+                .map(|(name, amount)| (name, amount / 60.0))
+                .collect(),
+        }
+    }
+
+    pub fn item_produced_per_sec(&self, item: &str) -> f64 {
+        for product in self.produced_per_sec() {
+            if product.0 == item {
+                return product.1;
+            }
+        }
+        0.0
+    }
+
+    /// Production of an item minus its consumption, recipes like kovarex enrichment consume their own product
+    pub fn item_net_produced_per_sec(&self, item: &str) -> f64 {
+        self.item_produced_per_sec(item) - self.item_consumed_per_sec(item)
+    }
+
+    pub fn item_consumed_per_sec(&self, item: &str) -> f64 {
+        self.consumed_per_sec()
+            .into_iter()
+            .filter(|(name, _)| name == item)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    pub fn item_produced_per_recipe(&self, item: &str) -> f64 {
+        match self.machine {
+            Machine::AssemblingMachine(_, re) => {
+                for product in &re.produces(self.difficulty) {
+                    if product.0 == item {
+                        return product.1;
+                    }
+                }
+                0.0
+            }
+            Machine::MiningDrill(_, re) => {
+                let products: Vec<(String, f64)> = (&re.results).into();
+                for product in &products {
+                    if product.0 == item {
+                        return product.1;
+                    }
+                }
+                0.0
+            }
+            Machine::RocketLaunch(_, payload, _) => payload
+                .launch_products()
+                .into_iter()
+                .filter(|(name, _)| name == item)
+                .map(|(_, amount)| amount)
+                .sum(),
+            Machine::OffshorePump(_)
+            | Machine::Boiler(_)
+            | Machine::Generator(_)
+            | Machine::Reactor(_) => 1.0,
+        }
+    }
+
+    pub fn consumed_per_sec(&self) -> Vec<(String, f64)> {
+        let mut consumed = match self.machine {
+            Machine::AssemblingMachine(_, r) => r
+                .consumes(self.difficulty)
+                .into_iter()
+                .map(|(name, amount)| (name, self.crafts_per_sec() * amount))
+                .collect(),
+            Machine::MiningDrill(_, re) => {
+                if let Some(fluid_requirement) = &re.fluid_requirement {
+                    vec![(
+                        fluid_requirement.required_fluid.clone(),
+                        fluid_requirement.fluid_amount * self.crafts_per_sec(),
+                    )]
+                } else {
+                    vec![]
+                }
+            }
+            Machine::Boiler(b) => {
+                let mut consumed = vec![(b.input_fluid(), b.fluid_per_sec())];
+                if b.energy_source.source_type == EnergySourceType::Heat {
+                    consumed.push((HEAT_ENERGY.into(), b.energy_consumption / ENERGY_UNIT));
+                }
+                consumed
+            }
+            Machine::Generator(g) => vec![(g.fluid(), g.fluid_per_sec())],
+            Machine::RocketLaunch(rs, payload, _) => vec![
+                (self.rocket_part(), rs.rocket_parts_required / 60.0),
+                (payload.name.clone(), 1.0 / 60.0),
+            ],
+            _ => vec![],
+        };
+        consumed.extend(self.fuel_per_sec());
+        consumed
+    }
+
+    /// Produced and consumed fluids, these are moved through pipes
+    pub fn fluids(&self) -> Vec<String> {
+        let fluids = match self.machine {
+            Machine::AssemblingMachine(_, recipe) => recipe.fluids(self.difficulty),
+            Machine::MiningDrill(_, resource) => resource.fluids(),
+            Machine::OffshorePump(op) => vec![op.fluid.as_str()],
+            Machine::Boiler(b) => return vec![b.input_fluid(), b.output_fluid()],
+            Machine::Generator(g) => return vec![g.fluid()],
+            Machine::Reactor(_) | Machine::RocketLaunch(_, _, _) => vec![],
+        };
+        fluids.into_iter().map(String::from).collect()
+    }
+
+    pub fn is_fluid(&self, item: &str) -> bool {
+        self.fluids().iter().any(|fluid| fluid == item)
+    }
+
+    pub fn icon_prefix(&self) -> &str {
+        match self.machine {
+            Machine::AssemblingMachine(_, _) => "assembling-machine",
+            Machine::MiningDrill(_, _) => "mining-drill",
+            Machine::OffshorePump(_) => "offshore-pump",
+            Machine::Boiler(_) => "boiler",
+            Machine::Generator(_) => "generator",
+            Machine::Reactor(_) => "reactor",
+            // Rocket silos are assembling machines too
+            Machine::RocketLaunch(_, _, _) => "assembling-machine",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => am.name.clone(),
+            Machine::MiningDrill(md, _) => md.name.clone(),
+            Machine::OffshorePump(op) => op.name.clone(),
+            Machine::Boiler(b) => b.name.clone(),
+            Machine::Generator(g) => g.name.clone(),
+            Machine::Reactor(r) => r.name.clone(),
+            Machine::RocketLaunch(rs, _, _) => rs.name.clone(),
+        }
+    }
+
+    /// Name of the recipe or resource, identifies the step regardless of the machine
+    pub fn recipe_name(&self) -> String {
+        match self.machine {
+            Machine::AssemblingMachine(_, recipe) => recipe.name.clone(),
+            Machine::MiningDrill(_, resource) => resource.name.clone(),
+            Machine::RocketLaunch(_, payload, _) => payload.name.clone(),
+            Machine::OffshorePump(_)
+            | Machine::Boiler(_)
+            | Machine::Generator(_)
+            | Machine::Reactor(_) => self.name(),
+        }
+    }
+
+    pub fn modules(&self) -> Option<&FactoryModules<'a>> {
+        match self.machine {
+            Machine::AssemblingMachine(_, _) | Machine::MiningDrill(_, _) => Some(&self.modules),
+            _ => None,
+        }
+    }
+
+    pub fn module_slots(&self) -> usize {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => am.module_slots(),
+            Machine::MiningDrill(md, _) => md.module_slots(),
+            _ => 0,
+        }
+    }
+
+    pub fn accepts_module(&self, module: &Module) -> bool {
+        match self.machine {
+            Machine::AssemblingMachine(am, recipe) => {
+                am.accepts_module(module) && module.allowed_in_recipe(&recipe.name)
+            }
+            // Allowed effects of mining drills are not exported, all of them accept any module,
+            // recipe limitations of productivity modules don't apply to mining
+            Machine::MiningDrill(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// Replace modules of this factory, modules that don't fit are dropped
+    pub fn with_modules(self, modules: Vec<&'a Module>) -> Self {
+        let modules: Vec<&Module> = modules
+            .into_iter()
+            .filter(|module| self.accepts_module(module))
+            .take(self.module_slots())
+            .collect();
+        self.replace_modules(|factory_modules| FactoryModules {
+            modules,
+            ..factory_modules
+        })
+    }
+
+    /// Replace beacons affecting this factory, modules that don't fit into the beacon are dropped
+    pub fn with_beacons(self, beacon: &'a Beacon, count: f64, modules: Vec<&'a Module>) -> Self {
+        let modules: Vec<&Module> = modules
+            .into_iter()
+            .filter(|module| beacon.accepts_module(module) && self.accepts_module(module))
+            .take(beacon.module_slots())
+            .collect();
+        self.replace_modules(|factory_modules| FactoryModules {
+            beacon: Some(beacon),
+            beacon_count: count.max(0.0),
+            beacon_modules: modules,
+            ..factory_modules
+        })
+    }
+
+    pub fn without_beacons(self) -> Self {
+        self.replace_modules(|factory_modules| FactoryModules {
+            beacon: None,
+            beacon_count: 0.0,
+            beacon_modules: Vec::new(),
+            ..factory_modules
+        })
+    }
+
+    fn replace_modules(mut self, f: impl FnOnce(FactoryModules<'a>) -> FactoryModules<'a>) -> Self {
+        if self.modules().is_some() {
+            self.modules = f(self.modules);
+        }
+        self
+    }
+
+    pub fn apply_beacon_setting(self, game_data: &'a GameData, setting: &BeaconSetting) -> Self {
+        match game_data.beacons.get(&setting.beacon) {
+            Some(beacon) => self.with_beacons(
+                beacon,
+                setting.count,
+                setting
+                    .modules
+                    .iter()
+                    .filter_map(|name| game_data.modules.get(name))
+                    .collect(),
+            ),
+            None => self.without_beacons(),
+        }
+    }
+
+    pub fn ips_for_item(game_data: &GameData, settings: &SolverSettings, item: &str) -> f64 {
+        if let Ok(factory) = Factory::for_item(game_data, settings, item) {
+            let amount = factory.item_net_produced_per_sec(item);
+            if amount > 0.0 {
+                return amount;
+            }
+        }
+        1.0
+    }
+
+    /// Crafting speed including speed bonus of modules
+    pub fn crafting_speed(&self) -> f64 {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => {
+                am.crafting_speed * self.modules.speed_multiplier()
+            }
+            Machine::MiningDrill(md, _) => md.mining_speed * self.modules.speed_multiplier(),
+            Machine::OffshorePump(op) => op.fluid_per_sec(),
+            _ => 1.0,
+        }
+    }
+
+    pub fn energy_required(&self) -> f64 {
+        match self.machine {
+            Machine::AssemblingMachine(_, recipe) => recipe.energy_required(self.difficulty),
+            Machine::MiningDrill(_, resource) => resource.mining_time,
+            _ => 1.0,
+        }
+    }
+
+    fn crafts_per_sec(&self) -> f64 {
+        self.crafting_speed() / self.energy_required()
+    }
+
+    fn energy_source(&self) -> Option<&'a EnergySource> {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => am.energy_source.as_ref(),
+            Machine::MiningDrill(md, _) => md.energy_source.as_ref(),
+            Machine::OffshorePump(op) => op.energy_source.as_ref(),
+            Machine::Boiler(b) => Some(&b.energy_source),
+            Machine::Generator(_) => None,
+            Machine::Reactor(r) => Some(&r.energy_source),
+            Machine::RocketLaunch(_, _, _) => None,
+        }
+    }
+
+    /// Energy used by one working machine per second, including module effects
+    pub fn energy_usage(&self) -> f64 {
+        let energy_usage = match self.machine {
+            Machine::AssemblingMachine(am, _) => am.energy_usage,
+            Machine::MiningDrill(md, _) => md.energy_usage,
+            Machine::OffshorePump(op) => op.energy_usage,
+            Machine::Boiler(b) => b.energy_consumption,
+            Machine::Generator(_) | Machine::RocketLaunch(_, _, _) => 0.0,
+            Machine::Reactor(r) => r.consumption,
+        };
+        energy_usage
+            * self
+                .modules()
+                .map_or(1.0, |modules| modules.consumption_multiplier())
+    }
+
+    /// Machines without a known energy source are assumed to be electric
+    pub fn is_electric(&self) -> bool {
+        self.energy_source()
+            .is_none_or(|source| source.source_type == EnergySourceType::Electric)
+    }
+
+    /// Game data without energy sources, like the trimmed dump, doesn't know the energy of crafting machines and drills
+    pub fn has_energy_data(&self) -> bool {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => am.energy_source.is_some(),
+            Machine::MiningDrill(md, _) => md.energy_source.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Electric power used by one working machine, in watts
+    pub fn power_usage(&self) -> f64 {
+        if self.is_electric() {
+            self.energy_usage()
+        } else {
+            0.0
+        }
+    }
+
+    /// Electric power used by one machine even when it is idle, in watts
+    pub fn drain(&self) -> f64 {
+        match self.machine {
+            Machine::AssemblingMachine(am, _) => am.drain(),
+            Machine::MiningDrill(md, _) => md.drain(),
+            _ => 0.0,
+        }
+    }
+
+    /// Fuel burnt by one working machine with a burner energy source
+    pub fn fuel_per_sec(&self) -> Option<(String, f64)> {
+        let source = self
+            .energy_source()
+            .filter(|source| source.source_type == EnergySourceType::Burner)?;
+        let fuel = self.fuel?;
+        Some((
+            fuel.name.clone(),
+            self.energy_usage() / (fuel.fuel_value * source.effectivity),
+        ))
+    }
+
+    /// Pick the fuel for machines with burner energy sources
+    pub fn with_fuel(mut self, game_data: &'a GameData, settings: &SolverSettings) -> Self {
+        self.fuel = self
+            .energy_source()
+            .filter(|source| source.source_type == EnergySourceType::Burner)
+            .and_then(|source| Self::find_fuel(game_data, settings, &source.fuel_categories()));
+        self
+    }
+
+    /// Preferred fuel if the machine can burn it, otherwise the first fuel that fits
+    fn find_fuel(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        fuel_categories: &[String],
+    ) -> Option<&'a Item> {
+        let preferred = settings
+            .fuel
+            .as_ref()
+            .and_then(|fuel| game_data.items.get(fuel));
+        preferred
+            .filter(|fuel| {
+                fuel.fuel_value > 0.0
+                    && fuel
+                        .fuel_category
+                        .as_ref()
+                        .is_some_and(|category| fuel_categories.contains(category))
+            })
+            .or_else(|| game_data.fuels(fuel_categories).into_iter().next())
+    }
+
+    /// Pollution emitted by one working machine, scales with its energy consumption
+    pub fn pollution_per_minute(&self) -> f64 {
+        let emissions = self
+            .energy_source()
+            .map_or(0.0, |source| source.emissions_per_minute);
+        emissions
+            * self.modules().map_or(1.0, |modules| {
+                modules.consumption_multiplier() * modules.pollution_multiplier()
+            })
+    }
+
+    /// Applies mining productivity research and the yield of infinite resources to mining drills
+    pub fn with_mining_bonus(mut self, settings: &SolverSettings) -> Self {
+        if let Machine::MiningDrill(_, resource) = self.machine {
+            self.mining_productivity = settings.mining_productivity();
+            if resource.infinite {
+                self.resource_yield = settings
+                    .resource_yields
+                    .get(&resource.name)
+                    .map_or(1.0, |percent| percent / 100.0)
+                    .max(resource.minimum_yield());
+            }
+        }
+        self
+    }
+
+    pub fn for_item(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        item: &str,
+    ) -> Result<Self, CalculationError> {
+        let difficulty = settings.difficulty;
+        let machine = if let Some(offshore_pump) =
+            Self::find_offshore_pump_for_item(game_data, settings, item)
+        {
+            Machine::OffshorePump(offshore_pump)
+        } else if item == ELECTRIC_ENERGY {
+            Self::find_generator(game_data, settings)
+                .map(Machine::Generator)
+                .ok_or_else(|| CalculationError::RecipeOrResourceNotFound(item.into()))?
+        } else if item == HEAT_ENERGY {
+            Self::find_reactor(game_data)
+                .map(Machine::Reactor)
+                .ok_or_else(|| CalculationError::RecipeOrResourceNotFound(item.into()))?
+        } else if let Some(resource) = Self::find_resource_for_item(game_data, item) {
+            let mining_drill = settings
+                .mining_drills
+                .get(&resource.category)
+                .and_then(|name| game_data.mining_drills.get(name))
+                .or_else(|| Self::find_mining_drill_for_resource(game_data, &resource.category))
+                .ok_or_else(|| CalculationError::MiningDrillNotFound(resource.category.clone()))?;
+            let modules = Self::default_modules(
+                game_data,
+                settings.resource_category_modules.get(&resource.category),
+            );
+            return Ok(
+                Self::new(Machine::MiningDrill(mining_drill, resource), difficulty)
+                    .with_modules(modules)
+                    .with_fuel(game_data, settings)
+                    .with_mining_bonus(settings),
+            );
+        } else if let Some(recipe) = Self::find_recipe_for_item(game_data, settings, item) {
+            let assembling_machine = settings
+                .assembling_machines
+                .get(&recipe.category)
+                .and_then(|name| game_data.assembling_machines.get(name))
+                .or_else(|| Self::find_assembling_machine_for_recipe(game_data, &recipe.category))
+                .ok_or_else(|| {
+                    CalculationError::AssemblingMachineNotFound(recipe.category.clone())
+                })?;
+            let modules = Self::default_modules(
+                game_data,
+                settings.recipe_category_modules.get(&recipe.category),
+            );
+            let factory = Self::new(
+                Machine::AssemblingMachine(assembling_machine, recipe),
+                difficulty,
+            )
+            .with_modules(modules)
+            .with_fuel(game_data, settings);
+            return Ok(
+                match settings.recipe_category_beacons.get(&recipe.category) {
+                    Some(setting) => factory.apply_beacon_setting(game_data, setting),
+                    None => factory,
+                },
+            );
+        } else if let Some((rocket_silo, payload)) =
+            Self::find_rocket_launch_for_item(game_data, item)
+        {
+            let rocket_part_recipe = rocket_silo
+                .fixed_recipe
+                .as_ref()
+                .and_then(|recipe| game_data.recipes.get(recipe));
+            Machine::RocketLaunch(rocket_silo, payload, rocket_part_recipe)
+        } else if let Some(boiler) = Self::find_boiler_for_fluid(game_data, settings, item) {
+            Machine::Boiler(boiler)
+        } else {
+            return Err(CalculationError::RecipeOrResourceNotFound(item.into()));
+        };
+        Ok(Self::new(machine, difficulty).with_fuel(game_data, settings))
+    }
+
+    fn default_modules(game_data: &'a GameData, names: Option<&Vec<String>>) -> Vec<&'a Module> {
+        names
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| game_data.modules.get(name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn find_recipe_for_item(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        item: &str,
+    ) -> Option<&'a Recipe> {
+        let is_enabled = |recipe: &Recipe| !settings.disabled_recipes.contains(&recipe.name);
+        if let Some(recipe) = settings
+            .item_recipes
+            .get(item)
+            .and_then(|name| game_data.recipes.get(name))
+            .or_else(|| Self::find_water_source_recipe(game_data, settings, item))
+            .filter(|recipe| is_enabled(recipe))
+        {
+            log::info!("Using preferred recipe {}", recipe.name);
+            return Some(recipe);
+        }
+        let recipe = game_data
+            .recipes_for_item(item, settings.difficulty)
+            .into_iter()
+            .find(|recipe| is_enabled(recipe));
+        if let Some(recipe) = recipe {
+            log::info!("Found recipe {}", recipe.name);
+        }
+        recipe
+    }
+
+    fn find_assembling_machine_for_recipe(
+        game_data: &'a GameData,
+        recipe_category: &str,
+    ) -> Option<&'a AssemblingMachine> {
+        game_data
+            .assembling_machines
+            .values()
+            .filter(|&assembling_machine| {
+                assembling_machine
+                    .crafting_categories
+                    .iter()
+                    .any(|c| c == recipe_category)
+            })
+            .min_by(|am1, am2| am1.name.cmp(&am2.name))
+    }
+
+    fn find_resource_for_item(game_data: &'a GameData, item: &str) -> Option<&'a Resource> {
+        game_data
+            .resources
+            .values()
+            .filter(|resource| {
+                let results: Vec<(String, f64)> = (&resource.results).into();
+                results.iter().any(|(x, _)| x == item)
+            })
+            .min_by(|r1, r2| r1.name.cmp(&r2.name))
+    }
+
+    fn find_mining_drill_for_resource(
+        game_data: &'a GameData,
+        resource_category: &str,
+    ) -> Option<&'a MiningDrill> {
+        game_data
+            .mining_drills
+            .values()
+            .filter(|&mining_drill| {
+                mining_drill
+                    .resource_categories
+                    .iter()
+                    .any(|c| c == resource_category)
+            })
+            .min_by(|md1, md2| md1.name.cmp(&md2.name))
+    }
+
+    fn find_rocket_launch_for_item(
+        game_data: &'a GameData,
+        item: &str,
+    ) -> Option<(&'a RocketSilo, &'a Item)> {
+        let rocket_silo = game_data
+            .rocket_silos
+            .values()
+            .min_by(|rs1, rs2| rs1.name.cmp(&rs2.name))?;
+        let mut payloads: Vec<&Item> = game_data
+            .items
+            .values()
+            .filter(|payload| {
+                payload
+                    .launch_products()
+                    .iter()
+                    .any(|(name, _)| name == item)
+            })
+            .collect();
+        payloads.sort_unstable_by(|p1, p2| p1.name.cmp(&p2.name));
+        payloads
+            .into_iter()
+            .next()
+            .map(|payload| (rocket_silo, payload))
+    }
+
+    /// Rocket part is the product of the recipe fixed in the silo
+    fn rocket_part(&self) -> String {
+        match self.machine {
+            Machine::RocketLaunch(_, _, Some(recipe)) => recipe
+                .produces(self.difficulty)
+                .into_iter()
+                .next()
+                .map_or_else(|| "rocket-part".into(), |(name, _)| name),
+            _ => "rocket-part".into(),
+        }
+    }
+
+    /// Boilers heating the fluid to the temperature of the generator come first, then by name.
+    /// Steam turbines get their steam from heat exchangers this way
+    fn find_boiler_for_fluid(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        fluid: &str,
+    ) -> Option<&'a Boiler> {
+        let temperature = Self::find_generator(game_data, settings)
+            .filter(|generator| generator.fluid() == fluid)
+            .map(|generator| generator.maximum_temperature);
+        let matches_generator =
+            |boiler: &Boiler| temperature.is_some_and(|t| boiler.target_temperature == t);
+        game_data
+            .boilers
+            .values()
+            .filter(|boiler| boiler.output_fluid() == fluid)
+            .min_by(|b1, b2| {
+                matches_generator(b2)
+                    .cmp(&matches_generator(b1))
+                    .then_with(|| b1.name.cmp(&b2.name))
+            })
+    }
+
+    /// Preferred generator, otherwise the first by name. Steam engines come before steam turbines
+    fn find_generator(game_data: &'a GameData, settings: &SolverSettings) -> Option<&'a Generator> {
+        settings
+            .generator
+            .as_ref()
+            .and_then(|name| game_data.generators.get(name))
+            .or_else(|| {
+                game_data
+                    .generators
+                    .values()
+                    .min_by(|g1, g2| g1.name.cmp(&g2.name))
+            })
+    }
+
+    fn find_reactor(game_data: &'a GameData) -> Option<&'a Reactor> {
+        game_data
+            .reactors
+            .values()
+            .min_by(|r1, r2| r1.name.cmp(&r2.name))
+    }
+
+    /// `None` if the preferred water source is a recipe
+    fn find_offshore_pump_for_item(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        item: &str,
+    ) -> Option<&'a OffshorePump> {
+        let offshore_pumps = game_data.offshore_pumps_for_fluid(item);
+        if Self::find_water_source_recipe(game_data, settings, item).is_some() {
+            return None;
+        }
+        match settings.water_sources.get(item) {
+            Some(source) => offshore_pumps
+                .iter()
+                .find(|offshore_pump| &offshore_pump.name == source)
+                .or_else(|| offshore_pumps.first())
+                .copied(),
+            None => offshore_pumps.first().copied(),
+        }
+    }
+
+    /// Recipe chosen as the water source of a fluid, offshore pumps can share names with recipes
+    fn find_water_source_recipe(
+        game_data: &'a GameData,
+        settings: &SolverSettings,
+        item: &str,
+    ) -> Option<&'a Recipe> {
+        settings
+            .water_sources
+            .get(item)
+            .and_then(|name| game_data.recipes.get(name))
+            .filter(|recipe| {
+                recipe
+                    .produces(settings.difficulty)
+                    .iter()
+                    .any(|(name, _)| name == item)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{
+        test_data::{self, from_json},
+        CalcTarget, CalcTargetRate, Calculation,
+    };
+    use hashbrown::HashMap;
+    use serde_json::json;
+
+    fn burner_game_data() -> GameData {
+        test_data::game_data(json!({
+            "items": {
+                "coal": {"icon": "coal.png", "name": "coal", "stack_size": 50, "fuel_value": "4MJ", "fuel_category": "chemical"},
+                "wood": {"icon": "wood.png", "name": "wood", "stack_size": 100, "fuel_value": "2MJ", "fuel_category": "chemical"},
+                "iron-ore": {"icon": "iron-ore.png", "name": "iron-ore", "stack_size": 50}
+            },
+            "mining_drills": {
+                "burner-mining-drill": {
+                    "icon": "burner-mining-drill.png",
+                    "name": "burner-mining-drill",
+                    "mining_speed": 0.25,
+                    "resource_categories": ["basic-solid"],
+                    "energy_usage": "150kW",
+                    "energy_source": {"type": "burner", "fuel_category": "chemical", "emissions_per_minute": 12}
+                }
+            },
+            "resources": {
+                "coal": {
+                    "icon": "coal.png",
+                    "name": "coal",
+                    "category": "basic-solid",
+                    "mining_time": 1.0,
+                    "result": "coal"
+                },
+                "iron-ore": {
+                    "icon": "iron-ore.png",
+                    "name": "iron-ore",
+                    "category": "basic-solid",
+                    "mining_time": 1.0,
+                    "result": "iron-ore"
+                }
+            }
+        }))
+    }
+
+    #[test]
+    fn burner_drill_burns_coal() {
+        let game_data = burner_game_data();
+        let factory =
+            Factory::for_item(&game_data, &SolverSettings::default(), "iron-ore").unwrap();
+        let (fuel, rate) = factory.fuel_per_sec().unwrap();
+        assert_eq!(fuel, "coal");
+        // 150kW from 4MJ of coal
+        assert!((rate - 0.0375).abs() < 1e-9);
+        assert!(factory
+            .consumed_per_sec()
+            .contains(&("coal".to_string(), rate)));
+        assert_eq!(factory.power_usage(), 0.0);
+    }
+
+    #[test]
+    fn burner_drill_burns_preferred_fuel() {
+        let game_data = burner_game_data();
+        let settings = SolverSettings {
+            fuel: Some("wood".into()),
+            ..SolverSettings::default()
+        };
+        let factory = Factory::for_item(&game_data, &settings, "iron-ore").unwrap();
+        assert_eq!(factory.fuel_per_sec(), Some(("wood".to_string(), 0.075)));
+    }
+
+    #[test]
+    fn coal_for_burner_drills_is_mined() {
+        let game_data = burner_game_data();
+        let calculation = Calculation::new(&game_data, SolverSettings::default())
+            .solve(&[CalcTarget::new(
+                "iron-ore",
+                CalcTargetRate::ItemsPerSecond(1.0),
+            )])
+            .unwrap();
+        // 4 drills on iron ore burn 0.15 coal/s, the coal drills burn some of what they mine
+        let coal_drills = 0.15 / (0.25 - 0.0375);
+        let inputs = calculation.inputs();
+        let coal = inputs.iter().find(|(name, _)| name == "coal").unwrap().1;
+        assert!((coal - coal_drills * 0.25).abs() < 1e-6, "{:?}", inputs);
+    }
+
+    #[test]
+    fn offshore_pump_rate_is_per_second() {
+        let game_data = power_game_data();
+        let factory = Factory::for_item(&game_data, &SolverSettings::default(), "water").unwrap();
+        assert_eq!(
+            factory.produced_per_sec(),
+            vec![("water".to_string(), 1200.0)]
+        );
+    }
+
+    #[test]
+    fn mining_drill_is_chosen_by_name() {
+        let mut game_data = burner_game_data();
+        game_data.mining_drills.insert(
+            "electric-mining-drill".into(),
+            from_json(json!({
+                "icon": "electric-mining-drill.png",
+                "name": "electric-mining-drill",
+                "mining_speed": 0.5,
+                "resource_categories": ["basic-solid"]
+            })),
+        );
+        let factory =
+            Factory::for_item(&game_data, &SolverSettings::default(), "iron-ore").unwrap();
+        assert_eq!(factory.name(), "burner-mining-drill");
+    }
+
+    #[test]
+    fn mining_drill_keeps_productivity_module() {
+        let game_data = burner_game_data();
+        let drill: MiningDrill = from_json(json!({
+            "icon": "electric-mining-drill.png",
+            "name": "electric-mining-drill",
+            "mining_speed": 0.5,
+            "resource_categories": ["basic-solid"],
+            "module_specification": {"module_slots": 3}
+        }));
+        let module: Module = from_json(json!({
+            "icon": "productivity-module.png",
+            "name": "productivity-module",
+            "category": "productivity",
+            "tier": 1,
+            "effect": {"productivity": {"bonus": 0.04}},
+            "limitation": ["iron-gear-wheel"]
+        }));
+        let resource = &game_data.resources["iron-ore"];
+        let factory = Factory::new(Machine::MiningDrill(&drill, resource), Difficulty::Normal)
+            .with_modules(vec![&module]);
+        assert_eq!(factory.modules().unwrap().modules, vec![&module]);
+    }
+
+    /// Burner fixture with a pumpjack on crude oil, an infinite resource
+    fn oil_game_data() -> GameData {
+        let mut game_data = burner_game_data();
+        game_data.mining_drills.insert(
+            "pumpjack".into(),
+            from_json(json!({
+                "icon": "pumpjack.png",
+                "name": "pumpjack",
+                "mining_speed": 1.0,
+                "resource_categories": ["basic-fluid"]
+            })),
+        );
+        game_data.resources.insert(
+            "crude-oil".into(),
+            from_json(json!({
+                "icon": "crude-oil.png",
+                "name": "crude-oil",
+                "category": "basic-fluid",
+                "mining_time": 1.0,
+                "results": [{"type": "fluid", "name": "crude-oil", "amount_min": 10, "amount_max": 10, "probability": 1}],
+                "infinite": true,
+                "minimum": 60000,
+                "normal": 300000
+            })),
+        );
+        game_data
+    }
+
+    fn oil_per_sec(game_data: &GameData, settings: &SolverSettings) -> f64 {
+        let factory = Factory::for_item(game_data, settings, "crude-oil").unwrap();
+        factory.produced_per_sec()[0].1
+    }
+
+    #[test]
+    fn infinite_resource_yield_is_applied() {
+        let game_data = oil_game_data();
+        assert!((oil_per_sec(&game_data, &SolverSettings::default()) - 10.0).abs() < 1e-9);
+        let settings = SolverSettings {
+            resource_yields: HashMap::from([("crude-oil".to_string(), 150.0)]),
+            ..SolverSettings::default()
+        };
+        assert!((oil_per_sec(&game_data, &settings) - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn infinite_resource_yield_is_clamped_to_minimum() {
+        let game_data = oil_game_data();
+        assert!((game_data.resources["crude-oil"].minimum_yield() - 0.2).abs() < 1e-12);
+        let settings = SolverSettings {
+            resource_yields: HashMap::from([("crude-oil".to_string(), 5.0)]),
+            ..SolverSettings::default()
+        };
+        assert!((oil_per_sec(&game_data, &settings) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mining_productivity_multiplies_yield() {
+        let game_data = oil_game_data();
+        let settings = SolverSettings {
+            mining_productivity_level: 2,
+            resource_yields: HashMap::from([
+                ("crude-oil".to_string(), 150.0),
+                ("iron-ore".to_string(), 50.0),
+            ]),
+            ..SolverSettings::default()
+        };
+        assert!((oil_per_sec(&game_data, &settings) - 18.0).abs() < 1e-9);
+        // Finite resources only get the productivity bonus
+        let drill = Factory::for_item(&game_data, &settings, "iron-ore").unwrap();
+        assert!((drill.produced_per_sec()[0].1 - 0.3).abs() < 1e-9);
+    }
+
+    /// Burner fixture with boilers, heat exchangers and the generators using their steam
+    fn power_game_data() -> GameData {
+        let mut game_data = burner_game_data();
+        game_data.offshore_pumps.insert(
+            "offshore-pump".into(),
+            from_json(json!({
+                "icon": "offshore-pump.png",
+                "name": "offshore-pump",
+                "fluid": "water",
+                "pumping_speed": 20
+            })),
+        );
+        game_data.boilers.insert(
+            "boiler".into(),
+            from_json(json!({
+                "icon": "boiler.png",
+                "name": "boiler",
+                "energy_consumption": "1.8MW",
+                "energy_source": {"type": "burner", "fuel_category": "chemical"},
+                "target_temperature": 165,
+                "fluid_box": {"filter": "water"},
+                "output_fluid_box": {"filter": "steam"}
+            })),
+        );
+        game_data.boilers.insert(
+            "heat-exchanger".into(),
+            from_json(json!({
+                "icon": "heat-exchanger.png",
+                "name": "heat-exchanger",
+                "energy_consumption": "10MW",
+                "energy_source": {"type": "heat"},
+                "target_temperature": 500,
+                "fluid_box": {"filter": "water"},
+                "output_fluid_box": {"filter": "steam"}
+            })),
+        );
+        game_data.generators.insert(
+            "steam-engine".into(),
+            from_json(json!({
+                "icon": "steam-engine.png",
+                "name": "steam-engine",
+                "fluid_usage_per_tick": 0.5,
+                "maximum_temperature": 165,
+                "fluid_box": {"filter": "steam"}
+            })),
+        );
+        game_data.generators.insert(
+            "steam-turbine".into(),
+            from_json(json!({
+                "icon": "steam-turbine.png",
+                "name": "steam-turbine",
+                "fluid_usage_per_tick": 1,
+                "maximum_temperature": 500,
+                "fluid_box": {"filter": "steam"}
+            })),
+        );
+        game_data.reactors.insert(
+            "nuclear-reactor".into(),
+            from_json(json!({
+                "icon": "nuclear-reactor.png",
+                "name": "nuclear-reactor",
+                "consumption": "40MW",
+                "energy_source": {"type": "burner", "fuel_category": "nuclear"}
+            })),
+        );
+        game_data
+    }
+
+    #[test]
+    fn steam_engine_uses_boiler_steam() {
+        let game_data = power_game_data();
+        let settings = SolverSettings::default();
+        let generator = Factory::for_item(&game_data, &settings, ELECTRIC_ENERGY).unwrap();
+        assert_eq!(generator.name(), "steam-engine");
+        let boiler = Factory::for_item(&game_data, &settings, "steam").unwrap();
+        assert_eq!(boiler.name(), "boiler");
+    }
+
+    #[test]
+    fn steam_turbine_uses_heat_exchanger_steam() {
+        let game_data = power_game_data();
+        let settings = SolverSettings {
+            generator: Some("steam-turbine".into()),
+            ..SolverSettings::default()
+        };
+        let calculation = Calculation::new(&game_data, settings)
+            .solve(&[CalcTarget::new(
+                ELECTRIC_ENERGY,
+                CalcTargetRate::ItemsPerSecond(10.0),
+            )])
+            .unwrap();
+        let mut machines: Vec<String> = calculation
+            .steps
+            .keys()
+            .map(|factory| factory.name())
+            .collect();
+        machines.sort();
+        assert_eq!(
+            machines,
+            [
+                "heat-exchanger",
+                "nuclear-reactor",
+                "offshore-pump",
+                "steam-turbine"
+            ]
+        );
+    }
+}
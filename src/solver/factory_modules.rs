@@ -0,0 +1,95 @@
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use super::MIN_EFFECT_MULTIPLIER;
+use crate::data::*;
+
+/// Modules inserted into a machine and into the beacons around it
+#[derive(Debug, Clone, Default)]
+pub struct FactoryModules<'a> {
+    pub modules: Vec<&'a Module>,
+    pub beacon: Option<&'a Beacon>,
+    pub beacon_count: f64,
+    pub beacon_modules: Vec<&'a Module>,
+}
+
+/// Beacons affecting a machine, stored by name so that it can be kept in settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BeaconSetting {
+    pub beacon: String,
+    pub count: f64,
+    pub modules: Vec<String>,
+}
+
+impl PartialEq for FactoryModules<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.names().eq(&other.names()) && self.beacon_setting().eq(&other.beacon_setting())
+    }
+}
+
+impl Eq for FactoryModules<'_> {}
+
+impl Hash for FactoryModules<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.names().hash(state);
+        if let Some(setting) = self.beacon_setting() {
+            setting.beacon.hash(state);
+            setting.count.to_bits().hash(state);
+            setting.modules.hash(state);
+        }
+    }
+}
+
+impl<'a> FactoryModules<'a> {
+    pub fn new(modules: Vec<&'a Module>) -> Self {
+        Self {
+            modules,
+            ..Default::default()
+        }
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.modules.iter().map(|m| m.name.clone()).collect()
+    }
+
+    pub fn beacon_setting(&self) -> Option<BeaconSetting> {
+        self.beacon.map(|beacon| BeaconSetting {
+            beacon: beacon.name.clone(),
+            count: self.beacon_count,
+            modules: self.beacon_modules.iter().map(|m| m.name.clone()).collect(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.beacon_modules.is_empty()
+    }
+
+    pub fn effect(&self, effect: EffectType) -> f64 {
+        let machine: f64 = self.modules.iter().map(|m| m.bonus(effect)).sum();
+        let beacons: f64 = match self.beacon {
+            Some(beacon) => {
+                let per_beacon: f64 = self.beacon_modules.iter().map(|m| m.bonus(effect)).sum();
+                per_beacon * beacon.distribution_effectivity * self.beacon_count
+            }
+            None => 0.0,
+        };
+        machine + beacons
+    }
+
+    pub fn speed_multiplier(&self) -> f64 {
+        (1.0 + self.effect(EffectType::Speed)).max(MIN_EFFECT_MULTIPLIER)
+    }
+
+    pub fn productivity_multiplier(&self) -> f64 {
+        1.0 + self.effect(EffectType::Productivity).max(0.0)
+    }
+
+    pub fn consumption_multiplier(&self) -> f64 {
+        (1.0 + self.effect(EffectType::Consumption)).max(MIN_EFFECT_MULTIPLIER)
+    }
+
+    pub fn pollution_multiplier(&self) -> f64 {
+        (1.0 + self.effect(EffectType::Pollution)).max(MIN_EFFECT_MULTIPLIER)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::data::*;
+
+/// Counts inserters of one type needed to move items in and out of machines,
+/// fluids and energy are not moved by inserters
+#[derive(Debug, Clone, PartialEq)]
+pub struct InserterCounter<'a> {
+    pub inserter: &'a Inserter,
+    /// Extra items carried in each swing from capacity bonus research
+    pub stack_bonus: u32,
+    /// Extra items carried in each swing by stack inserters
+    pub stack_inserter_bonus: u32,
+    game_data: &'a GameData,
+}
+
+impl<'a> InserterCounter<'a> {
+    /// Uses the named inserter, or the slowest one if there's no inserter with that name
+    pub fn new(
+        game_data: &'a GameData,
+        inserter: &str,
+        stack_bonus: u32,
+        stack_inserter_bonus: u32,
+    ) -> Option<Self> {
+        let inserter = game_data
+            .inserters
+            .get(inserter)
+            .or_else(|| game_data.inserters_sorted().into_iter().next())?;
+        Some(Self {
+            inserter,
+            stack_bonus,
+            stack_inserter_bonus,
+            game_data,
+        })
+    }
+
+    pub fn hand_size(&self) -> u32 {
+        if self.inserter.stack {
+            1 + self.stack_inserter_bonus
+        } else {
+            1 + self.stack_bonus
+        }
+    }
+
+    pub fn throughput(&self) -> f64 {
+        self.inserter.items_per_sec(self.hand_size())
+    }
+
+    /// Inserters needed for a flow in items per second, `None` if the item is not moved by inserters
+    pub fn inserters(&self, item: &str, rate: f64) -> Option<f64> {
+        self.game_data
+            .is_solid_item(item)
+            .then(|| rate / self.throughput())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::solver::test_data;
+
+    fn game_data() -> GameData {
+        test_data::game_data(json!({
+            "inserters": {
+                "inserter": {"icon": "inserter.png", "name": "inserter", "rotation_speed": 0.014},
+                "stack-inserter": {"icon": "stack-inserter.png", "name": "stack-inserter", "rotation_speed": 0.04, "stack": true}
+            }
+        }))
+    }
+
+    #[test]
+    fn regular_inserter_uses_stack_bonus() {
+        let game_data = game_data();
+        let inserters = InserterCounter::new(&game_data, "inserter", 2, 11).unwrap();
+        assert_eq!(inserters.hand_size(), 3);
+    }
+
+    #[test]
+    fn stack_inserter_uses_stack_inserter_bonus() {
+        let game_data = game_data();
+        let inserters = InserterCounter::new(&game_data, "stack-inserter", 2, 11).unwrap();
+        assert_eq!(inserters.hand_size(), 12);
+        assert!((inserters.throughput() - 0.04 * 60.0 * 12.0).abs() < 1e-9);
+    }
+}
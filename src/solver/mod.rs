@@ -0,0 +1,40 @@
+mod belts;
+mod calc_step;
+mod calc_target;
+mod calc_target_rate;
+mod calculation;
+mod factory;
+mod factory_modules;
+mod inserters;
+mod pipes;
+mod production_graph;
+mod research_target;
+mod robots;
+mod settings;
+mod simplex;
+#[cfg(test)]
+mod test_data;
+mod trains;
+
+pub use belts::*;
+pub use calc_step::*;
+pub use calc_target::*;
+pub use calc_target_rate::*;
+pub use calculation::*;
+pub use factory::*;
+pub use factory_modules::*;
+pub use inserters::*;
+pub use pipes::*;
+pub use production_graph::*;
+pub use research_target::*;
+pub use robots::*;
+pub use settings::*;
+pub use simplex::*;
+pub use trains::*;
+
+pub(crate) const SIMPLEX_ITERATION_LIMIT: usize = 50000;
+pub(crate) const VERY_SMALL: f64 = 1e-10;
+// Input limits count as used up when less than this part of them is left
+pub(crate) const RELATIVE_TOLERANCE: f64 = 1e-6;
+// Speed, consumption and pollution can't be reduced below 20% by modules
+pub(crate) const MIN_EFFECT_MULTIPLIER: f64 = 0.2;
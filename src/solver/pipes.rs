@@ -0,0 +1,105 @@
+use crate::data::*;
+
+// Fluid per second a pipeline can move, by its length in pipes between pumps
+const PIPE_THROUGHPUT: &[(u32, f64)] = &[
+    (1, 6000.0),
+    (2, 3000.0),
+    (3, 3000.0),
+    (7, 2000.0),
+    (12, 1500.0),
+    (17, 1200.0),
+    (20, 1090.0),
+    (30, 1000.0),
+    (50, 857.0),
+    (100, 666.0),
+    (150, 545.0),
+    (200, 461.0),
+    (261, 400.0),
+    (300, 375.0),
+    (400, 333.0),
+    (500, 300.0),
+    (600, 240.0),
+    (800, 187.0),
+    (1000, 150.0),
+];
+
+/// Counts pipelines and pumps needed to move fluid flows
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipeCounter<'a> {
+    pub pump: Option<&'a Pump>,
+}
+
+impl<'a> PipeCounter<'a> {
+    /// Uses the slowest pump, pipelines are only limited by pipes when there are no pumps
+    pub fn new(game_data: &'a GameData) -> Self {
+        let pump = game_data.pumps.values().min_by(|p1, p2| {
+            p1.pumping_speed
+                .total_cmp(&p2.pumping_speed)
+                .then_with(|| p1.name.cmp(&p2.name))
+        });
+        Self { pump }
+    }
+
+    /// Most fluid a single pipeline can move
+    pub fn pipeline_throughput(&self) -> f64 {
+        let pipe = PIPE_THROUGHPUT[0].1;
+        self.pump
+            .map_or(pipe, |pump| pump.fluid_per_sec().min(pipe))
+    }
+
+    /// Parallel pipelines needed for a flow in fluid per second
+    pub fn pipelines(&self, rate: f64) -> usize {
+        ((rate / self.pipeline_throughput()).ceil() as usize).max(1)
+    }
+
+    /// Longest pipe between pumps that keeps up with a flow split between the pipelines,
+    /// `None` if even the longest listed pipe is fast enough
+    pub fn max_pipe_length(&self, rate: f64) -> Option<u32> {
+        let per_pipeline = rate / self.pipelines(rate) as f64;
+        if per_pipeline <= PIPE_THROUGHPUT[PIPE_THROUGHPUT.len() - 1].1 {
+            return None;
+        }
+        PIPE_THROUGHPUT
+            .iter()
+            .rev()
+            .find(|(_, throughput)| *throughput >= per_pipeline)
+            .map(|(length, _)| *length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::test_data;
+    use serde_json::json;
+
+    #[test]
+    fn pipelines_are_limited_by_the_pump() {
+        let game_data = test_data::logistics_game_data();
+        let pipes = PipeCounter::new(&game_data);
+        assert_eq!(pipes.pipeline_throughput(), 1200.0);
+        assert_eq!(pipes.pipelines(3000.0), 3);
+        assert_eq!(pipes.pipelines(10.0), 1);
+    }
+
+    #[test]
+    fn pipelines_are_limited_by_pipes_without_pumps() {
+        let game_data = test_data::game_data(json!({}));
+        let pipes = PipeCounter::new(&game_data);
+        assert_eq!(pipes.pump, None);
+        assert_eq!(pipes.pipeline_throughput(), 6000.0);
+        assert_eq!(pipes.pipelines(7000.0), 2);
+        // 3500 per pipeline only fits through a single pipe
+        assert_eq!(pipes.max_pipe_length(7000.0), Some(1));
+    }
+
+    #[test]
+    fn max_pipe_length_keeps_up_with_the_flow() {
+        let game_data = test_data::logistics_game_data();
+        let pipes = PipeCounter::new(&game_data);
+        // 1000 per pipeline in each of the 3 pipelines
+        assert_eq!(pipes.max_pipe_length(3000.0), Some(30));
+        assert_eq!(pipes.max_pipe_length(300.0), Some(500));
+        assert_eq!(pipes.max_pipe_length(100.0), None);
+    }
+}
@@ -0,0 +1,270 @@
+use super::CalcStep;
+use hashbrown::{HashMap, HashSet};
+
+/// Flow of an item from one step to another, `from` and `to` are indices of the nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemFlow {
+    pub item: String,
+    pub from: usize,
+    pub to: usize,
+    pub rate: f64,
+    /// Fluids are moved through pipes instead of belts
+    pub fluid: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProductionGraph<'a> {
+    pub nodes: Vec<CalcStep<'a>>,
+    pub edges: Vec<ItemFlow>,
+}
+
+impl<'a> ProductionGraph<'a> {
+    pub fn new(mut nodes: Vec<CalcStep<'a>>) -> Self {
+        nodes.sort_by_cached_key(|step| {
+            let product = step
+                .produced_per_sec()
+                .into_iter()
+                .next()
+                .map(|(name, _)| name)
+                .unwrap_or_default();
+            (product, step.machine_name())
+        });
+
+        let mut producers: HashMap<String, Vec<(usize, f64)>> = HashMap::new();
+        let mut consumers: HashMap<String, Vec<(usize, f64)>> = HashMap::new();
+        for (idx, step) in nodes.iter().enumerate() {
+            for (name, amount) in step.produced_per_sec() {
+                producers.entry(name).or_default().push((idx, amount));
+            }
+            for (name, amount) in step.consumed_per_sec() {
+                consumers.entry(name).or_default().push((idx, amount));
+            }
+        }
+
+        // Every consumer takes from every producer of the item in proportion to what it produces
+        let mut edges = Vec::new();
+        for (item, item_consumers) in &consumers {
+            let item_producers = match producers.get(item) {
+                Some(item_producers) => item_producers,
+                None => continue,
+            };
+            let total: f64 = item_producers.iter().map(|(_, amount)| amount).sum();
+            let fluid = item_producers
+                .iter()
+                .any(|(from, _)| nodes[*from].factory.is_fluid(item));
+            for (to, consumed) in item_consumers {
+                for (from, produced) in item_producers {
+                    if from != to {
+                        edges.push(ItemFlow {
+                            item: item.clone(),
+                            from: *from,
+                            to: *to,
+                            rate: consumed * produced / total,
+                            fluid,
+                        });
+                    }
+                }
+            }
+        }
+        edges.sort_by(|e1, e2| {
+            (e1.from, e1.to)
+                .cmp(&(e2.from, e2.to))
+                .then_with(|| e1.item.cmp(&e2.item))
+        });
+
+        Self { nodes, edges }
+    }
+
+    /// Flows coming into a step
+    pub fn upstream(&self, node: usize) -> impl Iterator<Item = &ItemFlow> {
+        self.edges.iter().filter(move |edge| edge.to == node)
+    }
+
+    /// Flows going out of a step
+    pub fn downstream(&self, node: usize) -> impl Iterator<Item = &ItemFlow> {
+        self.edges.iter().filter(move |edge| edge.from == node)
+    }
+
+    /// Indices of the nodes ordered so that producers come before their consumers.
+    /// Loops are broken at the node with the fewest unresolved producers.
+    pub fn topological_order(&self) -> Vec<usize> {
+        let mut predecessors: Vec<HashSet<usize>> = vec![HashSet::new(); self.nodes.len()];
+        for edge in &self.edges {
+            predecessors[edge.to].insert(edge.from);
+        }
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut placed = vec![false; self.nodes.len()];
+        while let Some(idx) = (0..self.nodes.len())
+            .filter(|&idx| !placed[idx])
+            .min_by_key(|&idx| {
+                predecessors[idx]
+                    .iter()
+                    .filter(|&&pred| !placed[pred])
+                    .count()
+            })
+        {
+            placed[idx] = true;
+            order.push(idx);
+        }
+        order
+    }
+
+    /// Steps ordered from the final products down to the raw resources
+    pub fn ordered_steps(&self) -> Vec<CalcStep<'a>> {
+        self.topological_order()
+            .into_iter()
+            .rev()
+            .map(|idx| self.nodes[idx].clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::{Difficulty, GameData},
+        solver::{test_data, Factory, Machine},
+    };
+    use serde_json::json;
+
+    fn recipe(
+        name: &str,
+        ingredients: serde_json::Value,
+        results: serde_json::Value,
+    ) -> serde_json::Value {
+        json!({
+            "name": name,
+            "energy_required": 1.0,
+            "ingredients": ingredients,
+            "results": results
+        })
+    }
+
+    fn game_data() -> GameData {
+        test_data::game_data(json!({
+            "recipes": {
+                "iron-plate": recipe("iron-plate", json!([["iron-ore", 1.0]]), json!([["iron-plate", 1.0]])),
+                "iron-plate-from-scrap": recipe("iron-plate-from-scrap", json!([["scrap", 1.0]]), json!([["iron-plate", 1.0]])),
+                "iron-gear-wheel": recipe("iron-gear-wheel", json!([["iron-plate", 2.0]]), json!([["iron-gear-wheel", 1.0]])),
+                "pipe": recipe("pipe", json!([["iron-plate", 1.0]]), json!([["pipe", 1.0]])),
+                "water": recipe("water", json!([]), json!([{"type": "fluid", "name": "water", "amount": 10.0}])),
+                "steam": recipe("steam", json!([{"type": "fluid", "name": "water", "amount": 10.0}]), json!([{"type": "fluid", "name": "steam", "amount": 10.0}])),
+                "alpha": recipe("alpha", json!([["beta", 1.0]]), json!([["alpha", 2.0]])),
+                "beta": recipe("beta", json!([["alpha", 1.0]]), json!([["beta", 1.0]])),
+                "gamma": recipe("gamma", json!([["alpha", 1.0]]), json!([["gamma", 1.0]]))
+            },
+            "assembling_machines": {
+                "assembler": {
+                    "icon": "assembler.png",
+                    "name": "assembler",
+                    "crafting_categories": ["crafting"],
+                    "crafting_speed": 1.0
+                }
+            }
+        }))
+    }
+
+    /// Graph of the recipes made in one assembler each crafting once a second times `amount`
+    fn graph<'a>(game_data: &'a GameData, steps: &[(&str, f64)]) -> ProductionGraph<'a> {
+        let assembler = &game_data.assembling_machines["assembler"];
+        ProductionGraph::new(
+            steps
+                .iter()
+                .map(|(recipe, amount)| CalcStep {
+                    factory: Factory::new(
+                        Machine::AssemblingMachine(assembler, &game_data.recipes[*recipe]),
+                        Difficulty::Normal,
+                    ),
+                    amount: *amount,
+                })
+                .collect(),
+        )
+    }
+
+    fn node(graph: &ProductionGraph, recipe: &str) -> usize {
+        graph
+            .nodes
+            .iter()
+            .position(|step| step.factory.recipe_name() == recipe)
+            .unwrap()
+    }
+
+    fn rate(graph: &ProductionGraph, from: &str, to: &str) -> f64 {
+        let (from, to) = (node(graph, from), node(graph, to));
+        graph
+            .edges
+            .iter()
+            .filter(|edge| edge.from == from && edge.to == to)
+            .map(|edge| edge.rate)
+            .sum()
+    }
+
+    #[test]
+    fn consumers_share_producers_in_proportion() {
+        let game_data = game_data();
+        let graph = graph(
+            &game_data,
+            &[
+                ("iron-plate", 1.0),
+                ("iron-plate-from-scrap", 3.0),
+                ("iron-gear-wheel", 1.0),
+                ("pipe", 2.0),
+            ],
+        );
+        assert_eq!(graph.edges.len(), 4);
+        assert!((rate(&graph, "iron-plate", "iron-gear-wheel") - 0.5).abs() < 1e-9);
+        assert!((rate(&graph, "iron-plate-from-scrap", "iron-gear-wheel") - 1.5).abs() < 1e-9);
+        assert!((rate(&graph, "iron-plate", "pipe") - 0.5).abs() < 1e-9);
+        assert!((rate(&graph, "iron-plate-from-scrap", "pipe") - 1.5).abs() < 1e-9);
+        assert!(graph.edges.iter().all(|edge| !edge.fluid));
+    }
+
+    #[test]
+    fn fluid_flows_are_marked() {
+        let game_data = game_data();
+        let graph = graph(&game_data, &[("water", 1.0), ("steam", 1.0)]);
+        assert_eq!(
+            graph.edges,
+            vec![ItemFlow {
+                item: "water".into(),
+                from: node(&graph, "water"),
+                to: node(&graph, "steam"),
+                rate: 10.0,
+                fluid: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn producers_come_before_consumers() {
+        let game_data = game_data();
+        let graph = graph(
+            &game_data,
+            &[("iron-gear-wheel", 1.0), ("pipe", 1.0), ("iron-plate", 3.0)],
+        );
+        let order = graph.topological_order();
+        let position = |recipe| order.iter().position(|&idx| idx == node(&graph, recipe));
+        assert!(position("iron-plate") < position("iron-gear-wheel"));
+        assert!(position("iron-plate") < position("pipe"));
+        assert_eq!(graph.ordered_steps()[2].factory.recipe_name(), "iron-plate");
+    }
+
+    #[test]
+    fn recipe_loop_is_ordered_once() {
+        let game_data = game_data();
+        let graph = graph(&game_data, &[("gamma", 1.0), ("beta", 1.0), ("alpha", 1.0)]);
+        assert!(graph
+            .upstream(node(&graph, "alpha"))
+            .any(|edge| edge.item == "beta"));
+        assert!(graph
+            .upstream(node(&graph, "beta"))
+            .any(|edge| edge.item == "alpha"));
+        let mut order = graph.topological_order();
+        let position = |recipe| order.iter().position(|&idx| idx == node(&graph, recipe));
+        assert!(position("alpha") < position("gamma"));
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use super::{CalcTarget, CalcTargetRate};
+use crate::data::{Difficulty, GameData};
+
+/// Research that has to be supplied with science packs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResearchTarget {
+    /// Labs continuously researching a technology
+    Labs { technology: String, labs: f64 },
+    /// The same amount of every science pack of the set consumed per minute
+    SciencePerMinute {
+        science_packs: Vec<String>,
+        spm: f64,
+    },
+}
+
+impl ResearchTarget {
+    /// Science packs consumed by the research, in items per second
+    pub fn science_targets(&self, game_data: &GameData, difficulty: Difficulty) -> Vec<CalcTarget> {
+        let rates: Vec<(String, f64)> = match self {
+            Self::Labs { technology, labs } => match game_data.technologies.get(technology) {
+                Some(technology) => {
+                    let unit = technology.unit(difficulty);
+                    let ingredients = unit.ingredients();
+                    let science_packs: Vec<String> =
+                        ingredients.iter().map(|(name, _)| name.clone()).collect();
+                    let researching_speed = game_data
+                        .lab_for(&science_packs)
+                        .map_or(1.0, |lab| lab.researching_speed);
+                    let units_per_sec = labs * researching_speed / unit.time;
+                    ingredients
+                        .into_iter()
+                        .map(|(name, amount)| (name, amount * units_per_sec))
+                        .collect()
+                }
+                None => {
+                    log::warn!("Technology {} not found", technology);
+                    vec![]
+                }
+            },
+            Self::SciencePerMinute { science_packs, spm } => science_packs
+                .iter()
+                .map(|name| (name.clone(), spm / 60.0))
+                .collect(),
+        };
+        rates
+            .into_iter()
+            .map(|(name, items_per_second)| {
+                CalcTarget::new(name, CalcTargetRate::ItemsPerSecond(items_per_second))
+            })
+            .collect()
+    }
+
+    /// Science per minute of every science pack the labs accept
+    pub fn science_per_minute(game_data: &GameData, spm: f64) -> Self {
+        Self::SciencePerMinute {
+            science_packs: game_data.science_packs(),
+            spm,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::test_data;
+    use serde_json::json;
+
+    #[test]
+    fn science_per_minute_targets_every_pack() {
+        let game_data: GameData = test_data::game_data(json!({
+            "labs": {
+                "lab": {
+                    "icon": "lab.png",
+                    "name": "lab",
+                    "inputs": ["automation-science-pack", "logistic-science-pack"]
+                }
+            }
+        }));
+        let research = ResearchTarget::science_per_minute(&game_data, 30.0);
+        let targets = research.science_targets(&game_data, Difficulty::Normal);
+        assert_eq!(
+            targets,
+            vec![
+                CalcTarget::new(
+                    "automation-science-pack",
+                    CalcTargetRate::ItemsPerSecond(0.5)
+                ),
+                CalcTarget::new("logistic-science-pack", CalcTargetRate::ItemsPerSecond(0.5)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::Calculation;
+use crate::data::*;
+
+// Speed bonus of each worker robot speed research level, later levels repeat the last one
+const SPEED_RESEARCH_BONUS: &[f64] = &[0.35, 0.4, 0.45, 0.55, 0.65];
+
+/// Where robots fly and how far their research got
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct RobotSetting {
+    /// Average distance from a provider to a requester in tiles
+    pub distance: f64,
+    pub speed_level: u32,
+    /// Every worker robot cargo size research level adds one item to the payload
+    pub cargo_level: u32,
+}
+
+impl Default for RobotSetting {
+    fn default() -> Self {
+        Self {
+            distance: 50.0,
+            speed_level: 0,
+            cargo_level: 0,
+        }
+    }
+}
+
+impl RobotSetting {
+    pub fn speed_bonus(&self) -> f64 {
+        (0..self.speed_level as usize)
+            .map(|level| SPEED_RESEARCH_BONUS[level.min(SPEED_RESEARCH_BONUS.len() - 1)])
+            .sum()
+    }
+}
+
+/// Estimates logistic robots needed to move item flows, charging is not taken into account
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobotCounter<'a> {
+    pub robot: &'a LogisticRobot,
+    pub setting: RobotSetting,
+    game_data: &'a GameData,
+}
+
+impl<'a> RobotCounter<'a> {
+    pub fn new(game_data: &'a GameData, setting: RobotSetting) -> Option<Self> {
+        let robot = game_data
+            .logistic_robots
+            .values()
+            .min_by(|r1, r2| r1.name.cmp(&r2.name))?;
+        Some(Self {
+            robot,
+            setting,
+            game_data,
+        })
+    }
+
+    pub fn payload(&self) -> u32 {
+        self.robot.max_payload_size + self.setting.cargo_level
+    }
+
+    /// Seconds to fly to the requester and back
+    pub fn trip_time(&self) -> f64 {
+        let tiles_per_sec = self.robot.speed * 60.0 * (1.0 + self.setting.speed_bonus());
+        self.setting.distance * 2.0 / tiles_per_sec
+    }
+
+    /// Robots needed for a flow in items per second, `None` if the item is not carried by robots
+    pub fn robots(&self, item: &str, rate: f64) -> Option<f64> {
+        self.game_data
+            .is_solid_item(item)
+            .then(|| rate * self.trip_time() / self.payload() as f64)
+    }
+
+    /// Robots carrying every item to the steps consuming it, both between steps and from imported inputs
+    pub fn robots_per_item(&self, calculation: &Calculation) -> Vec<(String, f64)> {
+        let graph = calculation.graph();
+        // Mined inputs are already moved by the flows from the mining drills
+        let produced: HashSet<String> = graph
+            .nodes
+            .iter()
+            .flat_map(|step| step.produced_per_sec())
+            .map(|(name, _)| name)
+            .collect();
+        let mut flows: BTreeMap<String, f64> = BTreeMap::new();
+        for flow in graph.edges {
+            *flows.entry(flow.item).or_default() += flow.rate;
+        }
+        for (item, rate) in calculation.inputs() {
+            if !produced.contains(&item) {
+                *flows.entry(item).or_default() += rate;
+            }
+        }
+        flows
+            .into_iter()
+            .filter_map(|(item, rate)| {
+                let robots = self.robots(&item, rate)?;
+                Some((item, robots))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::test_data::logistics_game_data;
+
+    #[test]
+    fn speed_research_repeats_the_last_level() {
+        let setting = RobotSetting {
+            speed_level: 7,
+            ..RobotSetting::default()
+        };
+        assert!((setting.speed_bonus() - 3.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn robots_for_a_flow() {
+        let game_data = logistics_game_data();
+        let robots = RobotCounter::new(
+            &game_data,
+            RobotSetting {
+                distance: 30.0,
+                speed_level: 0,
+                cargo_level: 2,
+            },
+        )
+        .unwrap();
+        assert_eq!(robots.payload(), 3);
+        // 3 tiles per second there and back
+        assert!((robots.trip_time() - 20.0).abs() < 1e-9);
+        assert!((robots.robots("iron-plate", 3.0).unwrap() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fluids_and_energy_are_not_carried() {
+        let game_data = logistics_game_data();
+        let robots = RobotCounter::new(&game_data, RobotSetting::default()).unwrap();
+        assert_eq!(robots.robots("water", 100.0), None);
+        assert_eq!(robots.robots(ELECTRIC_ENERGY, 100.0), None);
+        assert_eq!(robots.robots(HEAT_ENERGY, 100.0), None);
+    }
+}
@@ -0,0 +1,73 @@
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+
+use super::BeaconSetting;
+use crate::data::Difficulty;
+
+/// Preferences used when choosing machines, recipes and modules for the steps. Prototypes are referenced by name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolverSettings {
+    pub difficulty: Difficulty,
+    /// Assembling machine for every recipe category
+    pub assembling_machines: HashMap<String, String>,
+    /// Mining drill for every resource category
+    pub mining_drills: HashMap<String, String>,
+    /// Recipe for items that have more than one
+    pub item_recipes: HashMap<String, String>,
+    pub disabled_recipes: HashSet<String>,
+    pub recipe_category_modules: HashMap<String, Vec<String>>,
+    pub resource_category_modules: HashMap<String, Vec<String>>,
+    pub recipe_category_beacons: HashMap<String, BeaconSetting>,
+    /// Fuel burnt by machines with burner energy sources, the first fitting fuel is used when it's not set
+    pub fuel: Option<String>,
+    /// Source for fluids pumped by offshore pumps, either an offshore pump or a recipe like a water well.
+    /// The offshore pump with the first name is used when it's not set
+    pub water_sources: HashMap<String, String>,
+    /// Generator making electricity, the one with the first name is used when it's not set.
+    /// Boilers heating its fluid to the temperature it uses are preferred
+    pub generator: Option<String>,
+    /// Levels of mining productivity research, every level adds 10% to all mining drills
+    pub mining_productivity_level: u32,
+    /// Yield of infinite resources in percent of their normal amount, 100% when not set
+    pub resource_yields: HashMap<String, f64>,
+}
+
+/// Recipes disabled by default, these loop back into their own ingredients
+pub const DEFAULT_DISABLED_RECIPES: &[&str] = &[
+    "coal-liquefaction",
+    "kovarex-enrichment-process",
+    "nuclear-fuel-reprocessing",
+];
+
+// Productivity added by every level of mining productivity research
+const MINING_PRODUCTIVITY_PER_LEVEL: f64 = 0.1;
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::default(),
+            assembling_machines: HashMap::new(),
+            mining_drills: HashMap::new(),
+            item_recipes: HashMap::new(),
+            disabled_recipes: DEFAULT_DISABLED_RECIPES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            recipe_category_modules: HashMap::new(),
+            resource_category_modules: HashMap::new(),
+            recipe_category_beacons: HashMap::new(),
+            fuel: None,
+            water_sources: HashMap::new(),
+            generator: None,
+            mining_productivity_level: 0,
+            resource_yields: HashMap::new(),
+        }
+    }
+}
+
+impl SolverSettings {
+    pub fn mining_productivity(&self) -> f64 {
+        self.mining_productivity_level as f64 * MINING_PRODUCTIVITY_PER_LEVEL
+    }
+}
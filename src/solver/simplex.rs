@@ -0,0 +1,356 @@
+use super::{SIMPLEX_ITERATION_LIMIT, VERY_SMALL};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintKind {
+    LessOrEqual,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl ConstraintKind {
+    fn flipped(self) -> Self {
+        match self {
+            Self::LessOrEqual => Self::GreaterOrEqual,
+            Self::GreaterOrEqual => Self::LessOrEqual,
+            Self::Equal => Self::Equal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    pub coefficients: Vec<f64>,
+    pub kind: ConstraintKind,
+    pub rhs: f64,
+}
+
+/// Linear program in the form "minimize `objective * x` subject to `constraints`, `x >= 0`"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinearProgram {
+    pub objective: Vec<f64>,
+    pub constraints: Vec<Constraint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum SimplexError {
+    #[error("Constraints can't be satisfied")]
+    Infeasible,
+    #[error("Objective is unbounded")]
+    Unbounded,
+    #[error("Iteration limit reached")]
+    IterationLimit,
+}
+
+impl LinearProgram {
+    pub fn new(objective: Vec<f64>) -> Self {
+        Self {
+            objective,
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn add_constraint(&mut self, coefficients: Vec<f64>, kind: ConstraintKind, rhs: f64) {
+        self.constraints.push(Constraint {
+            coefficients,
+            kind,
+            rhs,
+        })
+    }
+
+    /// Solve using the two-phase simplex method, returns values of the variables
+    pub fn minimize(&self) -> Result<Vec<f64>, SimplexError> {
+        Tableau::new(self).solve()
+    }
+
+    pub fn maximize(&self) -> Result<Vec<f64>, SimplexError> {
+        let negated = Self {
+            objective: self.objective.iter().map(|c| -c).collect(),
+            constraints: self.constraints.clone(),
+        };
+        negated.minimize()
+    }
+}
+
+#[derive(Debug)]
+struct Tableau {
+    // Each row is a constraint, last value in a row is the right hand side
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+    variables: usize,
+    // Columns starting from this one are artificial variables
+    first_artificial: usize,
+    objective: Vec<f64>,
+}
+
+impl Tableau {
+    fn new(lp: &LinearProgram) -> Self {
+        let variables = lp.objective.len();
+        let constraints: Vec<(Vec<f64>, ConstraintKind, f64)> = lp
+            .constraints
+            .iter()
+            .map(|c| {
+                let mut coefficients = c.coefficients.clone();
+                coefficients.resize(variables, 0.0);
+                if c.rhs < 0.0 {
+                    (
+                        coefficients.into_iter().map(|v| -v).collect(),
+                        c.kind.flipped(),
+                        -c.rhs,
+                    )
+                } else {
+                    (coefficients, c.kind, c.rhs)
+                }
+            })
+            .collect();
+
+        let slacks = constraints
+            .iter()
+            .filter(|(_, kind, _)| *kind != ConstraintKind::Equal)
+            .count();
+        let artificials = constraints
+            .iter()
+            .filter(|(_, kind, _)| *kind != ConstraintKind::LessOrEqual)
+            .count();
+        let first_artificial = variables + slacks;
+        let width = first_artificial + artificials + 1;
+
+        let mut rows = Vec::with_capacity(constraints.len());
+        let mut basis = Vec::with_capacity(constraints.len());
+        let mut slack_idx = variables;
+        let mut artificial_idx = first_artificial;
+        for (coefficients, kind, rhs) in constraints {
+            let mut row = coefficients;
+            row.resize(width, 0.0);
+            row[width - 1] = rhs;
+            match kind {
+                ConstraintKind::LessOrEqual => {
+                    row[slack_idx] = 1.0;
+                    basis.push(slack_idx);
+                    slack_idx += 1;
+                }
+                ConstraintKind::GreaterOrEqual => {
+                    row[slack_idx] = -1.0;
+                    slack_idx += 1;
+                    row[artificial_idx] = 1.0;
+                    basis.push(artificial_idx);
+                    artificial_idx += 1;
+                }
+                ConstraintKind::Equal => {
+                    row[artificial_idx] = 1.0;
+                    basis.push(artificial_idx);
+                    artificial_idx += 1;
+                }
+            }
+            rows.push(row);
+        }
+
+        let mut objective = lp.objective.clone();
+        objective.resize(width, 0.0);
+
+        Self {
+            rows,
+            basis,
+            variables,
+            first_artificial,
+            objective,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.objective.len()
+    }
+
+    fn solve(mut self) -> Result<Vec<f64>, SimplexError> {
+        let width = self.width();
+
+        // Phase 1: minimize the sum of artificial variables
+        let mut phase_one = vec![0.0; width];
+        for cost in &mut phase_one[self.first_artificial..width - 1] {
+            *cost = 1.0;
+        }
+        let mut reduced = self.reduced_costs(&phase_one);
+        self.run(&mut reduced, width - 1)?;
+        if -reduced[width - 1] > VERY_SMALL.sqrt() {
+            return Err(SimplexError::Infeasible);
+        }
+        self.drive_out_artificials();
+
+        // Phase 2: minimize the original objective, artificial variables can't enter the basis anymore
+        let objective = self.objective.clone();
+        let mut reduced = self.reduced_costs(&objective);
+        self.run(&mut reduced, self.first_artificial)?;
+
+        let mut result = vec![0.0; self.variables];
+        for (row, &var) in self.rows.iter().zip(&self.basis) {
+            if var < self.variables {
+                result[var] = row[width - 1].max(0.0);
+            }
+        }
+        Ok(result)
+    }
+
+    fn reduced_costs(&self, costs: &[f64]) -> Vec<f64> {
+        let mut reduced = costs.to_vec();
+        for (row, &var) in self.rows.iter().zip(&self.basis) {
+            let cost = costs[var];
+            if cost != 0.0 {
+                for (r, v) in reduced.iter_mut().zip(row) {
+                    *r -= cost * v;
+                }
+            }
+        }
+        reduced
+    }
+
+    fn run(&mut self, reduced: &mut [f64], entering_limit: usize) -> Result<(), SimplexError> {
+        let rhs = self.width() - 1;
+        for _ in 0..SIMPLEX_ITERATION_LIMIT {
+            // Bland's rule: smallest index is picked both for entering and leaving variables, which prevents cycling
+            let entering = match (0..entering_limit).find(|&j| reduced[j] < -VERY_SMALL) {
+                Some(j) => j,
+                None => return Ok(()),
+            };
+            let mut leaving: Option<(usize, f64)> = None;
+            for (i, row) in self.rows.iter().enumerate() {
+                if row[entering] > VERY_SMALL {
+                    let ratio = row[rhs] / row[entering];
+                    leaving = match leaving {
+                        Some((l, best))
+                            if best < ratio - VERY_SMALL
+                                || ((best - ratio).abs() <= VERY_SMALL
+                                    && self.basis[l] < self.basis[i]) =>
+                        {
+                            Some((l, best))
+                        }
+                        _ => Some((i, ratio)),
+                    };
+                }
+            }
+            let (leaving, _) = leaving.ok_or(SimplexError::Unbounded)?;
+            self.pivot(leaving, entering, reduced);
+        }
+        Err(SimplexError::IterationLimit)
+    }
+
+    fn pivot(&mut self, row_idx: usize, col_idx: usize, reduced: &mut [f64]) {
+        let pivot = self.rows[row_idx][col_idx];
+        for v in &mut self.rows[row_idx] {
+            *v /= pivot;
+        }
+        let pivot_row = self.rows[row_idx].clone();
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            let factor = row[col_idx];
+            if i != row_idx && factor != 0.0 {
+                for (v, p) in row.iter_mut().zip(&pivot_row) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        let factor = reduced[col_idx];
+        if factor != 0.0 {
+            for (v, p) in reduced.iter_mut().zip(&pivot_row) {
+                *v -= factor * p;
+            }
+        }
+        self.basis[row_idx] = col_idx;
+    }
+
+    fn drive_out_artificials(&mut self) {
+        let mut dummy = vec![0.0; self.width()];
+        let mut redundant = Vec::new();
+        for row_idx in 0..self.rows.len() {
+            if self.basis[row_idx] < self.first_artificial {
+                continue;
+            }
+            match (0..self.first_artificial).find(|&j| self.rows[row_idx][j].abs() > VERY_SMALL) {
+                Some(col_idx) => self.pivot(row_idx, col_idx, &mut dummy),
+                // Every coefficient is zero, the constraint is a linear combination of other constraints
+                None => redundant.push(row_idx),
+            }
+        }
+        for row_idx in redundant.into_iter().rev() {
+            self.rows.remove(row_idx);
+            self.basis.remove(row_idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn feasible_minimum() {
+        // Optimum is where both constraints meet
+        let mut lp = LinearProgram::new(vec![1.0, 1.0]);
+        lp.add_constraint(vec![1.0, 2.0], ConstraintKind::GreaterOrEqual, 4.0);
+        lp.add_constraint(vec![3.0, 1.0], ConstraintKind::GreaterOrEqual, 6.0);
+        assert_close(&lp.minimize().unwrap(), &[1.6, 1.2]);
+    }
+
+    #[test]
+    fn equality_and_redundant_constraints() {
+        let mut lp = LinearProgram::new(vec![1.0, 2.0]);
+        lp.add_constraint(vec![1.0, 1.0], ConstraintKind::Equal, 3.0);
+        lp.add_constraint(vec![2.0, 2.0], ConstraintKind::Equal, 6.0);
+        lp.add_constraint(vec![0.0, 1.0], ConstraintKind::GreaterOrEqual, 1.0);
+        assert_close(&lp.minimize().unwrap(), &[2.0, 1.0]);
+    }
+
+    #[test]
+    fn negative_right_hand_side() {
+        let mut lp = LinearProgram::new(vec![1.0]);
+        lp.add_constraint(vec![-1.0], ConstraintKind::LessOrEqual, -2.5);
+        assert_close(&lp.minimize().unwrap(), &[2.5]);
+    }
+
+    #[test]
+    fn maximum() {
+        let mut lp = LinearProgram::new(vec![3.0, 2.0]);
+        lp.add_constraint(vec![1.0, 1.0], ConstraintKind::LessOrEqual, 4.0);
+        lp.add_constraint(vec![1.0, 3.0], ConstraintKind::LessOrEqual, 6.0);
+        lp.add_constraint(vec![1.0, 0.0], ConstraintKind::LessOrEqual, 3.0);
+        assert_close(&lp.maximize().unwrap(), &[3.0, 1.0]);
+    }
+
+    #[test]
+    fn infeasible() {
+        let mut lp = LinearProgram::new(vec![1.0]);
+        lp.add_constraint(vec![1.0], ConstraintKind::LessOrEqual, 1.0);
+        lp.add_constraint(vec![1.0], ConstraintKind::GreaterOrEqual, 2.0);
+        assert_eq!(lp.minimize(), Err(SimplexError::Infeasible));
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut lp = LinearProgram::new(vec![-1.0, 0.0]);
+        lp.add_constraint(vec![1.0, -1.0], ConstraintKind::GreaterOrEqual, 1.0);
+        assert_eq!(lp.minimize(), Err(SimplexError::Unbounded));
+    }
+
+    #[test]
+    fn degenerate_problem_does_not_cycle() {
+        // Beale's example, cycles forever with the textbook pivoting rule
+        let mut lp = LinearProgram::new(vec![-0.75, 20.0, -0.5, 6.0]);
+        lp.add_constraint(
+            vec![0.25, -8.0, -1.0, 9.0],
+            ConstraintKind::LessOrEqual,
+            0.0,
+        );
+        lp.add_constraint(
+            vec![0.5, -12.0, -0.5, 3.0],
+            ConstraintKind::LessOrEqual,
+            0.0,
+        );
+        lp.add_constraint(vec![0.0, 0.0, 1.0, 0.0], ConstraintKind::LessOrEqual, 1.0);
+        assert_close(&lp.minimize().unwrap(), &[1.0, 0.0, 1.0, 0.0]);
+    }
+}
@@ -0,0 +1,57 @@
+//! Game data fixtures shared by the solver tests
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::data::GameData;
+
+/// Game data made of the given sections, the required sections that are left out are empty
+pub fn game_data(sections: Value) -> GameData {
+    let mut data = json!({
+        "items": {},
+        "recipes": {},
+        "assembling_machines": {},
+        "item_groups": {},
+        "item_subgroups": {},
+        "mining_drills": {},
+        "offshore_pumps": {},
+        "resources": {}
+    });
+    if let (Some(data), Value::Object(sections)) = (data.as_object_mut(), sections) {
+        data.extend(sections);
+    }
+    from_json(data)
+}
+
+pub fn from_json<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
+
+/// Belts, pumps, robots and wagons moving iron plates, stone and water
+pub fn logistics_game_data() -> GameData {
+    game_data(json!({
+        "items": {
+            "iron-plate": {"icon": "iron-plate.png", "name": "iron-plate", "stack_size": 100},
+            "stone": {"icon": "stone.png", "name": "stone", "stack_size": 50}
+        },
+        "offshore_pumps": {
+            "offshore-pump": {"icon": "offshore-pump.png", "name": "offshore-pump", "fluid": "water", "pumping_speed": 20}
+        },
+        "transport_belts": {
+            "transport-belt": {"icon": "transport-belt.png", "name": "transport-belt", "speed": 0.03125},
+            "fast-transport-belt": {"icon": "fast-transport-belt.png", "name": "fast-transport-belt", "speed": 0.0625}
+        },
+        "pumps": {
+            "pump": {"icon": "pump.png", "name": "pump", "pumping_speed": 20}
+        },
+        "logistic_robots": {
+            "logistic-robot": {"icon": "logistic-robot.png", "name": "logistic-robot", "speed": 0.05, "max_payload_size": 1}
+        },
+        "cargo_wagons": {
+            "cargo-wagon": {"icon": "cargo-wagon.png", "name": "cargo-wagon", "inventory_size": 40}
+        },
+        "fluid_wagons": {
+            "fluid-wagon": {"icon": "fluid-wagon.png", "name": "fluid-wagon", "capacity": 25000}
+        }
+    }))
+}
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::data::*;
+
+/// Wagons and trains needed to bring in a flow
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TrainEstimate {
+    /// Items or fluid a single wagon carries
+    pub wagon_capacity: f64,
+    pub wagons_per_minute: f64,
+    /// Trains of the given length in circulation to keep up with the flow
+    pub trains: f64,
+}
+
+/// Counts wagons and trains needed to move inputs, uses the largest cargo and fluid wagons
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainCounter<'a> {
+    pub cargo_wagon: Option<&'a CargoWagon>,
+    pub fluid_wagon: Option<&'a FluidWagon>,
+    game_data: &'a GameData,
+    fluids: HashSet<&'a str>,
+}
+
+impl<'a> TrainCounter<'a> {
+    pub fn new(game_data: &'a GameData) -> Self {
+        let cargo_wagon = game_data.cargo_wagons.values().max_by(|w1, w2| {
+            w1.inventory_size
+                .cmp(&w2.inventory_size)
+                .then_with(|| w2.name.cmp(&w1.name))
+        });
+        let fluid_wagon = game_data.fluid_wagons.values().max_by(|w1, w2| {
+            w1.capacity
+                .total_cmp(&w2.capacity)
+                .then_with(|| w2.name.cmp(&w1.name))
+        });
+        Self {
+            cargo_wagon,
+            fluid_wagon,
+            game_data,
+            fluids: game_data.fluids(),
+        }
+    }
+
+    /// Items or fluid one wagon carries, `None` if there's no wagon for it
+    pub fn wagon_capacity(&self, item: &str) -> Option<f64> {
+        if self.fluids.contains(item) {
+            return self.fluid_wagon.map(|wagon| wagon.capacity);
+        }
+        let stack_size = self.game_data.items.get(item)?.stack_size;
+        if stack_size == 0 {
+            return None;
+        }
+        self.cargo_wagon
+            .map(|wagon| (wagon.inventory_size * stack_size) as f64)
+    }
+
+    /// Trains for a flow in items per second, with the round trip time in seconds
+    /// and the number of wagons in each train
+    pub fn estimate(
+        &self,
+        item: &str,
+        rate: f64,
+        trip_time: f64,
+        wagons_per_train: u32,
+    ) -> Option<TrainEstimate> {
+        let wagon_capacity = self.wagon_capacity(item)?;
+        let wagons_per_minute = rate * 60.0 / wagon_capacity;
+        let trains = rate * trip_time / (wagon_capacity * wagons_per_train.max(1) as f64);
+        Some(TrainEstimate {
+            wagon_capacity,
+            wagons_per_minute,
+            trains,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::test_data::logistics_game_data;
+
+    #[test]
+    fn items_use_cargo_wagons() {
+        let game_data = logistics_game_data();
+        let trains = TrainCounter::new(&game_data);
+        assert_eq!(
+            trains.estimate("iron-plate", 10.0, 120.0, 4),
+            Some(TrainEstimate {
+                wagon_capacity: 4000.0,
+                wagons_per_minute: 0.15,
+                trains: 0.075,
+            })
+        );
+    }
+
+    #[test]
+    fn fluids_use_fluid_wagons() {
+        let game_data = logistics_game_data();
+        let trains = TrainCounter::new(&game_data);
+        assert_eq!(trains.wagon_capacity("water"), Some(25000.0));
+        let estimate = trains.estimate("water", 1250.0, 60.0, 0).unwrap();
+        assert_eq!(estimate.wagons_per_minute, 3.0);
+        // Trains have at least one wagon
+        assert_eq!(estimate.trains, 3.0);
+    }
+
+    #[test]
+    fn energy_is_not_moved_by_trains() {
+        let game_data = logistics_game_data();
+        let trains = TrainCounter::new(&game_data);
+        assert_eq!(trains.estimate(ELECTRIC_ENERGY, 1.0, 60.0, 4), None);
+        assert_eq!(trains.estimate(HEAT_ENERGY, 1.0, 60.0, 4), None);
+    }
+}